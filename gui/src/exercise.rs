@@ -0,0 +1,271 @@
+use crate::visual::{result_maximum, Visualizeable};
+use structmech::stiffness::envelope::InternalForceField;
+use structmech::stiffness::exercise::{Exercise, ExerciseKind};
+use structmech::stiffness::system::*;
+
+/// The drawing of the system without the closing `\end{tikzpicture}`.
+fn open_drawing(sys: &System) -> String {
+    let s = sys.visualize();
+    let mut s = s.replace("\\end{tikzpicture}\n", "");
+    // Momentengelenke an den Stabenden
+    let mut hinges = vec![false; sys.get_points().len()];
+    for b in 0..sys.get_beams().len() {
+        let dofs = sys.get_beams()[b].get_dofs();
+        if dofs[2] {
+            hinges[sys.get_beam_from_point(b)] = true;
+        }
+        if dofs[5] {
+            hinges[sys.get_beam_to_point(b)] = true;
+        }
+    }
+    for p in 0..hinges.len() {
+        if hinges[p] {
+            s.push_str(&format!("  \\hinge{{1}}{{p{}}}\n", p));
+        }
+    }
+    return s;
+}
+
+/// The start face and the unit vectors along and perpendicular to the beam in TikZ
+/// coordinates.
+fn beam_frame(sys: &System, b: usize) -> ([f64; 2], [f64; 2], [f64; 2]) {
+    let start = sys.get_beam_start_face(b);
+    let alpha = sys.get_beam_alpha(b);
+    (
+        [start.x, start.y],
+        [alpha.cos(), alpha.sin()],
+        // Die lokale z-Achse, die Lasten zeigen nach unten
+        [alpha.sin(), -alpha.cos()],
+    )
+}
+
+fn round(v: f64) -> f64 {
+    let r = (v * 100.0).round() / 100.0;
+    if r == 0.0 {
+        0.0
+    } else {
+        r
+    }
+}
+
+impl Visualizeable for Exercise {
+    /// The problem drawing with the loads, the values are in kN and m.
+    fn visualize(&self) -> String {
+        let sys = self.get_system();
+        let loading = self.get_loading();
+        let mut s = open_drawing(sys);
+
+        for i in 0..loading.get_static_loads().len() {
+            let p = loading.get_static_load_points()[i];
+            let [fx, fy, m] = loading.get_static_loads()[i].get_loading();
+            let f = (fx * fx + fy * fy).sqrt();
+            if f > 0.0 {
+                s.push_str(&format!(
+                    "  \\draw[-latex, very thick] (p{0}) ++({1:.4},{2:.4}) node[fill=white, inner sep=1pt] {{${3}$}} -- (p{0});\n",
+                    p,
+                    -fx / f,
+                    fy / f,
+                    round(f)
+                ));
+            }
+            if m != 0.0 {
+                s.push_str(&format!(
+                    "  \\node[above right] at (p{}) {{$M = {}$}};\n",
+                    p,
+                    round(m)
+                ));
+            }
+        }
+
+        let max = loading
+            .get_lineloads()
+            .iter()
+            .flat_map(|q| {
+                [
+                    q.get_from_perpendicular_load(),
+                    q.get_to_perpendicular_load(),
+                ]
+            })
+            .fold(0.0_f64, |m, q| m.max(q.abs()));
+        for i in 0..loading.get_lineloads().len() {
+            let b = loading.get_loaded_beams()[i];
+            let q = &loading.get_lineloads()[i];
+            let (a, e, z) = beam_frame(sys, b);
+            let l = sys.get_beam_lenght(b);
+            let h = [
+                0.8 * q.get_from_perpendicular_load() / max,
+                0.8 * q.get_to_perpendicular_load() / max,
+            ];
+            let at = |x: f64, h: f64| {
+                format!(
+                    "({:.4},{:.4})",
+                    a[0] + x * e[0] - h * z[0],
+                    a[1] + x * e[1] - h * z[1]
+                )
+            };
+            // Die Last wirkt in Richtung der lokalen z-Achse
+            s.push_str(&format!(
+                "  \\draw {} -- {} -- {} -- {};\n",
+                at(0.0, 0.0),
+                at(0.0, h[0]),
+                at(l, h[1]),
+                at(l, 0.0)
+            ));
+            let arrows = (l / 0.5).ceil() as usize;
+            for k in 0..=arrows {
+                let x = l * k as f64 / arrows as f64;
+                let hx = h[0] + (h[1] - h[0]) * x / l;
+                if hx.abs() > 0.05 {
+                    s.push_str(&format!(
+                        "  \\draw[-latex] {} -- {};\n",
+                        at(x, hx),
+                        at(x, 0.0)
+                    ));
+                }
+            }
+            let label = if q.get_from_perpendicular_load() == q.get_to_perpendicular_load() {
+                format!("{}", round(q.get_from_perpendicular_load()))
+            } else {
+                format!(
+                    "{} \\ldots {}",
+                    round(q.get_from_perpendicular_load()),
+                    round(q.get_to_perpendicular_load())
+                )
+            };
+            s.push_str(&format!(
+                "  \\node[fill=white, inner sep=1pt] at {} {{${}$}};\n",
+                at(l / 2.0, 0.5 * (h[0] + h[1]) + 0.2 * (h[0] + h[1]).signum()),
+                label
+            ));
+        }
+        s.push_str("\\end{tikzpicture}\n");
+        return s;
+    }
+}
+
+/// The diagram of the internal force `dof_s` (0 = N, 1 = Q, 2 = M) on the side of the local z
+/// axis, with the values at the ends of the beams.
+pub fn visualize_result_tikz<R: InternalForceField>(
+    sys: &System,
+    res: &R,
+    samples: u64,
+    dof_s: usize,
+) -> String {
+    let mut s = open_drawing(sys);
+    let max = result_maximum(res, samples, dof_s);
+    let r = if max > 0.0 { 1.0 / max } else { 0.0 };
+    for b in 0..res.get_beam_count() {
+        let l = res.get_beam_lenght(b);
+        let (a, e, z) = beam_frame(sys, b);
+        let at = |x: f64, v: f64| {
+            format!(
+                "({:.4},{:.4})",
+                a[0] + x * e[0] + v * r * z[0],
+                a[1] + x * e[1] + v * r * z[1]
+            )
+        };
+        s.push_str(&format!("  \\draw[red, fill=red!15] {}", at(0.0, 0.0)));
+        for sam in 0..=samples {
+            let x = l * sam as f64 / samples as f64;
+            s.push_str(&format!(" -- {}", at(x, res.get_internals_at(b, x)[dof_s])));
+        }
+        s.push_str(&format!(" -- {};\n", at(l, 0.0)));
+        for x in [0.0, l] {
+            let v = res.get_internals_at(b, x)[dof_s];
+            if v.abs() > 1e-6 * max {
+                s.push_str(&format!(
+                    "  \\node[red, font=\\footnotesize] at {} {{${}$}};\n",
+                    at(x, v * (1.0 + 0.3 * max / v.abs())),
+                    round(v)
+                ));
+            }
+        }
+    }
+    s.push_str("\\end{tikzpicture}\n");
+    return s;
+}
+
+/// A LaTeX document with the exercise and the answer key, the support reactions, the end forces
+/// and the diagrams of N, Q and M. It needs the packages `stanli` and `amsmath`.
+pub fn exercise_sheet(ex: &Exercise) -> String {
+    let sys = ex.get_system();
+    let mut s = String::from(
+        "\\documentclass{article}\n\\usepackage{amsmath}\n\\usepackage{stanli}\n\\begin{document}\n\n",
+    );
+
+    // Aufgabe
+    let kind = match ex.get_kind() {
+        ExerciseKind::ContinuousBeam => "continuous beam",
+        ExerciseKind::ThreeHingedFrame => "frame",
+        ExerciseKind::Truss => "truss",
+    };
+    s.push_str("\\section*{Exercise}\n\n");
+    s.push_str(&format!(
+        "Determine the support reactions and the internal forces of the {}. All beams have \
+         $E = {}$~kN/m$^2$, $A = {}$~m$^2$ and $I = {}$~m$^4$, forces are in kN and lengths in \
+         m.\n\n",
+        kind,
+        sys.get_beams()[0].get_emodul(),
+        sys.get_beams()[0].get_area(),
+        sys.get_beams()[0].get_ftm()
+    ));
+    s.push_str("\\begin{center}\n");
+    s.push_str(&ex.visualize());
+    s.push_str("\\end{center}\n\n");
+    s.push_str("\\begin{tabular}{rrr}\nPoint & $x$ & $y$ \\\\ \\hline\n");
+    for (i, p) in sys.get_points().iter().enumerate() {
+        s.push_str(&format!("{} & {} & {} \\\\\n", i, p.x, p.y));
+    }
+    s.push_str("\\end{tabular}\n\n");
+
+    // Lösung
+    s.push_str("\\newpage\n\\section*{Solution}\n\n");
+    s.push_str(&format!(
+        "The system is {}-fold statically indeterminate.\n\n",
+        ex.get_indeterminacy()
+    ));
+    s.push_str("\\subsection*{Support reactions}\n\n");
+    s.push_str("$H$ acts to the right, $V$ upwards and $M$ clockwise on the system.\n\n");
+    s.push_str("\\begin{tabular}{rrrr}\nPoint & $H$ & $V$ & $M$ \\\\ \\hline\n");
+    for i in 0..sys.get_supports().len() {
+        let r = ex.get_support_reactions()[i];
+        s.push_str(&format!(
+            "{} & {} & {} & {} \\\\\n",
+            sys.get_support_points()[i],
+            round(r[0]),
+            // Nach oben positiv
+            round(-r[1]),
+            round(r[2])
+        ));
+    }
+    s.push_str("\\end{tabular}\n\n");
+    s.push_str("\\subsection*{End forces}\n\n");
+    s.push_str(
+        "\\begin{tabular}{rrrrrrrrr}\nBeam & from & to & $N_a$ & $Q_a$ & $M_a$ & $N_b$ & $Q_b$ & $M_b$ \\\\ \\hline\n",
+    );
+    let forces = ex.get_end_forces();
+    for b in 0..forces.len() {
+        s.push_str(&format!(
+            "{} & {} & {}",
+            b,
+            sys.get_beam_from_point(b),
+            sys.get_beam_to_point(b)
+        ));
+        for v in forces[b] {
+            s.push_str(&format!(" & {}", round(v)));
+        }
+        s.push_str(" \\\\\n");
+    }
+    s.push_str("\\end{tabular}\n\n");
+
+    for (dof, name) in ["Normal force $N$", "Shear force $Q$", "Bending moment $M$"]
+        .iter()
+        .enumerate()
+    {
+        s.push_str(&format!("\\subsection*{{{}}}\n\n\\begin{{center}}\n", name));
+        s.push_str(&visualize_result_tikz(sys, ex.get_results(), 20, dof));
+        s.push_str("\\end{center}\n\n");
+    }
+    s.push_str("\\end{document}\n");
+    return s;
+}
@@ -1,3 +1,4 @@
+pub mod exercise;
 pub mod structly;
 pub mod util;
 pub mod visual;
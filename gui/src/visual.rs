@@ -1,5 +1,6 @@
 use std::f64::consts;
-use structmech::stiffness::direct_stiffness::BeamResultSet;
+use structmech::stiffness::envelope::{Envelope, InternalForceField};
+use structmech::stiffness::influence::InfluenceLine;
 use structmech::stiffness::system::*;
 
 pub fn visualize_asymptote(sys: &System) -> String {
@@ -116,59 +117,123 @@ void anschluss(pair z1, pair z2, real beam_degree, bool[] dof, real dof_degree){
     return s;
 }
 
-pub fn visualize_result_asymptote(
+pub fn visualize_result_asymptote<R: InternalForceField>(
     sys: &System,
-    res: &BeamResultSet,
+    res: &R,
     samples: u64,
     dof_s: usize,
 ) -> String {
     let mut s = String::new();
     s.push_str(&visualize_asymptote(sys));
 
-    // Zuerst müssen wir alle Werte in Erfahrung bringen.
-    let mut resultsMoment = Vec::new();
-    for b in 0..res.get_results().len() {
-        let resu = &res.get_results()[b];
-        let l = resu.get_beam_lenght();
+    // Der Kehrwert des Maximums ist der Skalierer.
+    let r = 1.0 / result_maximum(res, samples, dof_s);
+    s.push_str(&result_paths(sys, res, samples, dof_s, r, "b", "red"));
+    return s;
+}
+
+/// Draws the upper and the lower bound of an envelope with the same scale.
+pub fn visualize_envelope_asymptote(
+    sys: &System,
+    env: &Envelope,
+    samples: u64,
+    dof_s: usize,
+) -> String {
+    let mut s = String::new();
+    s.push_str(&visualize_asymptote(sys));
+
+    let max = env.get_max_field();
+    let min = env.get_min_field();
+    let r = 1.0 / result_maximum(&max, samples, dof_s).max(result_maximum(&min, samples, dof_s));
+    s.push_str(&result_paths(sys, &max, samples, dof_s, r, "bmax", "red"));
+    s.push_str(&result_paths(sys, &min, samples, dof_s, r, "bmin", "blue"));
+    return s;
+}
 
-        let mut moment = Vec::new();
+pub(crate) fn result_maximum<R: InternalForceField>(res: &R, samples: u64, dof_s: usize) -> f64 {
+    let mut max = 0.0_f64;
+    for b in 0..res.get_beam_count() {
+        let l = res.get_beam_lenght(b);
         for sam in 0..=samples {
             let inter = sam as f64 / samples as f64;
-            let m = resu.get_internals_at(l * inter)[dof_s];
-            moment.push(m);
-        }
-        resultsMoment.push(moment);
-    }
-    // Anschließend müssen wir das Maximum in erfahrung brigen.
-    let mut max = 0.0_f64;
-    for m in resultsMoment {
-        for f in m {
-            max = max.max(f.abs());
+            max = max.max(res.get_internals_at(b, l * inter)[dof_s].abs());
         }
     }
-    // Dessen Kehrwert der Skalierer ist.
-    let r = 1.0 / max;
-    // Jetzt der zweite durchlauf
-    for b in 0..res.get_results().len() {
-        let beam = sys.get_beams()[b];
-        let resu = &res.get_results()[b];
-        let l = resu.get_beam_lenght();
+    return max;
+}
 
-        let start = sys.get_beam_from_point(b);
+fn result_paths<R: InternalForceField>(
+    sys: &System,
+    res: &R,
+    samples: u64,
+    dof_s: usize,
+    r: f64,
+    name: &str,
+    color: &str,
+) -> String {
+    let mut s = String::new();
+    for b in 0..res.get_beam_count() {
+        let l = res.get_beam_lenght(b);
+
+        // Die Ergebnisse beginnen am Anschnitt, nicht am Knoten.
+        let start = sys.get_beam_start_face(b);
         let alph = sys.get_beam_alpha(b) / consts::PI * 180.0;
 
-        s.push_str(&format!("path b{} = (0.0,0.0) --", b));
+        s.push_str(&format!("path {}{} = (0.0,0.0) --", name, b));
 
         for sam in 0..=samples {
             let inter = sam as f64 / samples as f64;
-            let m = resu.get_internals_at(l * inter)[dof_s];
+            let m = res.get_internals_at(b, l * inter)[dof_s];
             s.push_str(&format!("({0:.5},{1:.5}) -- ", l * inter, m * r));
         }
         s.push_str(&format!("({},0.0) -- cycle;\n", l));
 
         s.push_str(&format!(
-            "draw(rotate({},p{})*shift(p{})*b{},red);\n",
-            alph, start, start, b
+            "draw(rotate({0},({1},{2}))*shift(({1},{2}))*{3}{4},{5});\n",
+            alph, start.x, start.y, name, b, color
+        ));
+    }
+    return s;
+}
+
+/// Draws an influence line along the beams of its load path.
+pub fn visualize_influence_asymptote(sys: &System, line: &InfluenceLine) -> String {
+    let mut s = String::new();
+    s.push_str(&visualize_asymptote(sys));
+
+    let mut max = 0.0_f64;
+    for v in line.get_values() {
+        max = max.max(v.abs());
+    }
+    let r = 1.0 / max;
+
+    let beams = line.get_beams();
+    let mut i = 0;
+    while i < beams.len() {
+        let b = beams[i];
+        let l = sys.get_beam_lenght(b);
+        let start = sys.get_beam_start_face(b);
+        let alph = sys.get_beam_alpha(b) / consts::PI * 180.0;
+
+        s.push_str(&format!("path i{} = (0.0,0.0) --", i));
+        // Der Anfangswert ist beim vorherigen Stab
+        if line.get_positions()[i] > 0.0 && i > 0 {
+            s.push_str(&format!("(0.0,{0:.5}) -- ", line.get_values()[i - 1] * r));
+        }
+        let first = i;
+        while i < beams.len() && beams[i] == b {
+            s.push_str(&format!(
+                "({0:.5},{1:.5}) -- ",
+                line.get_positions()[i],
+                line.get_values()[i] * r
+            ));
+            i += 1;
+        }
+        s.push_str(&format!("({},0.0) -- cycle;\n", l));
+
+        s.push_str(&format!(
+            "draw(rotate({0},({1},{2}))*shift(({1},{2}))*i{3},red);\n",
+            alph, start.x, start.y, first
         ));
     }
     return s;
@@ -0,0 +1,150 @@
+use crate::stiffness::direct_stiffness::BeamResultSet;
+use crate::stiffness::system::*;
+use libm::cos;
+
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-6;
+
+/// The state of a cable at the end of the iteration.
+pub struct CableResult {
+    beam: usize,
+    tension: f64,
+    sag: f64,
+    ernst_modulus: f64,
+    active: bool,
+}
+
+impl CableResult {
+    pub fn get_beam(&self) -> usize {
+        self.beam
+    }
+    pub fn get_tension(&self) -> f64 {
+        self.tension
+    }
+    /// The vertical sag at midspan `q l^2 / (8 N)`, infinite for a slack cable.
+    pub fn get_sag(&self) -> f64 {
+        self.sag
+    }
+    /// The ideal modulus of elasticity after Ernst used for the final state.
+    pub fn get_ernst_modulus(&self) -> f64 {
+        self.ernst_modulus
+    }
+    /// Whether the cable is tensioned, slack cables do not take part in the load transfer.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+}
+
+pub struct CableResultSet {
+    results: BeamResultSet,
+    cables: Vec<CableResult>,
+    iterations: usize,
+    converged: bool,
+}
+
+impl CableResultSet {
+    pub fn get_results(&self) -> &BeamResultSet {
+        &self.results
+    }
+    pub fn get_cables(&self) -> &[CableResult] {
+        &self.cables
+    }
+    pub fn get_iterations(&self) -> usize {
+        self.iterations
+    }
+    pub fn is_converged(&self) -> bool {
+        self.converged
+    }
+}
+
+impl System {
+    /// Solves the system with its cables by iterating the Ernst modulus
+    /// `E_i = E / (1 + (q l_h)^2 E A / (12 N^3))` of every cable.
+    ///
+    /// Slack cables are deactivated and reactivated, if they are stretched again. With
+    /// `second_order` every iteration uses second order theory for the remaining beams.
+    pub fn matrix_stiffness_method_cable(
+        &self,
+        loading: &SystemLoading,
+        second_order: bool,
+    ) -> CableResultSet {
+        let mut sys = self.clone();
+        let solve = |sys: &System| {
+            if second_order {
+                sys.matrix_stiffness_method_second_order(loading)
+            } else {
+                sys.matrix_stiffness_method_first_order(loading)
+            }
+        };
+        let cables: Vec<usize> = (0..self.get_beams().len())
+            .filter(|&i| self.get_beams()[i].is_cable())
+            .collect();
+
+        let mut iterations = 0;
+        let mut converged = false;
+        let mut res = solve(&sys);
+
+        while iterations < MAX_ITERATIONS {
+            iterations += 1;
+            let mut changed = false;
+            for &i in &cables {
+                let b = sys.get_beams()[i];
+                let r = &res.get_results()[i];
+                let active = if b.is_active() {
+                    r.get_rsks()[0] > 0.0
+                } else {
+                    r.get_rvs()[3] - r.get_rvs()[0] > 0.0
+                };
+                if active != b.is_active() {
+                    sys.get_beams_mut()[i].set_active(active);
+                    changed = true;
+                }
+                if active && b.is_active() {
+                    let e = self.ernst_modulus(i, r.get_rsks()[0]);
+                    if ((e - b.get_emodul()) / b.get_emodul()).abs() > TOLERANCE {
+                        sys.get_beams_mut()[i].set_emodul(e);
+                        changed = true;
+                    }
+                }
+            }
+            if !changed {
+                converged = true;
+                break;
+            }
+            res = solve(&sys);
+        }
+
+        let mut result = Vec::new();
+        for &i in &cables {
+            let b = sys.get_beams()[i];
+            let n = res.get_results()[i].get_rsks()[0];
+            let l = self.get_beam_lenght(i);
+            result.push(CableResult {
+                beam: i,
+                tension: n,
+                sag: if b.is_active() {
+                    b.get_cable_weight() * l * l / (8.0 * n)
+                } else {
+                    f64::INFINITY
+                },
+                ernst_modulus: b.get_emodul(),
+                active: b.is_active(),
+            });
+        }
+
+        CableResultSet {
+            results: res,
+            cables: result,
+            iterations,
+            converged,
+        }
+    }
+
+    fn ernst_modulus(&self, beamindex: usize, tension: f64) -> f64 {
+        let b = &self.get_beams()[beamindex];
+        let e = b.get_emodul();
+        let l_h = self.get_beam_lenght(beamindex) * cos(self.get_beam_alpha(beamindex)).abs();
+        let q = b.get_cable_weight();
+        e / (1.0 + (q * l_h).powi(2) * e * b.get_area() / (12.0 * tension.powi(3)))
+    }
+}
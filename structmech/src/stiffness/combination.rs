@@ -0,0 +1,291 @@
+use crate::stiffness::direct_stiffness::BeamResultSet;
+use crate::stiffness::system::*;
+
+/// The type of an action after EN 1990, the combination factors are taken from table A1.1.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ActionType {
+    Permanent,
+    /// Settlements, which are permanent actions with their own partial factor.
+    Settlement,
+    /// Imposed loads of the categories A (domestic) to H (roofs).
+    ImposedA,
+    ImposedB,
+    ImposedC,
+    ImposedD,
+    ImposedE,
+    ImposedF,
+    ImposedG,
+    ImposedH,
+    /// Snow on sites at altitude H <= 1000 m a.s.l.
+    Snow,
+    /// Snow on sites at altitude H > 1000 m a.s.l.
+    SnowHighAltitude,
+    Wind,
+    Temperature,
+    /// A variable action with the given factors [psi_0, psi_1, psi_2].
+    Variable([f64; 3]),
+}
+
+impl ActionType {
+    pub fn is_permanent(&self) -> bool {
+        matches!(self, ActionType::Permanent | ActionType::Settlement)
+    }
+    /// The combination factors [psi_0, psi_1, psi_2], zero for permanent actions.
+    pub fn get_psi(&self) -> [f64; 3] {
+        match self {
+            ActionType::Permanent | ActionType::Settlement => [0.0, 0.0, 0.0],
+            ActionType::ImposedA | ActionType::ImposedB => [0.7, 0.5, 0.3],
+            ActionType::ImposedC | ActionType::ImposedD => [0.7, 0.7, 0.6],
+            ActionType::ImposedE => [1.0, 0.9, 0.8],
+            ActionType::ImposedF => [0.7, 0.7, 0.6],
+            ActionType::ImposedG => [0.7, 0.5, 0.3],
+            ActionType::ImposedH => [0.0, 0.0, 0.0],
+            ActionType::Snow => [0.5, 0.2, 0.0],
+            ActionType::SnowHighAltitude => [0.7, 0.5, 0.2],
+            ActionType::Wind => [0.6, 0.2, 0.0],
+            ActionType::Temperature => [0.6, 0.5, 0.0],
+            ActionType::Variable(psi) => *psi,
+        }
+    }
+}
+
+/// A named load case, e.g. dead load, live load, wind, snow or settlement.
+#[derive(Clone)]
+pub struct LoadCase {
+    name: String,
+    action: ActionType,
+    loading: SystemLoading,
+}
+
+impl LoadCase {
+    pub fn new(name: &str, action: ActionType, loading: SystemLoading) -> Self {
+        LoadCase {
+            name: name.to_string(),
+            action,
+            loading,
+        }
+    }
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn get_action(&self) -> ActionType {
+        self.action
+    }
+    pub fn get_loading(&self) -> &SystemLoading {
+        &self.loading
+    }
+}
+
+/// The design situations of EN 1990, which are generated automatically.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CombinationType {
+    /// Persistent and transient design situation (6.10) of the ultimate limit state.
+    Fundamental,
+    /// Characteristic combination (6.14b) of the serviceability limit state.
+    Characteristic,
+    /// Frequent combination (6.15b) of the serviceability limit state.
+    Frequent,
+    /// Quasi-permanent combination (6.16b) of the serviceability limit state.
+    QuasiPermanent,
+}
+
+/// The partial factors of the ultimate limit state, set B (STR/GEO) of EN 1990 table A1.2(B).
+#[derive(Clone, Copy)]
+pub struct PartialFactors {
+    gamma_g_sup: f64,
+    gamma_g_inf: f64,
+    gamma_q: f64,
+    gamma_g_set: f64,
+}
+
+impl PartialFactors {
+    pub fn new(gamma_g_sup: f64, gamma_g_inf: f64, gamma_q: f64, gamma_g_set: f64) -> Self {
+        PartialFactors {
+            gamma_g_sup,
+            gamma_g_inf,
+            gamma_q,
+            gamma_g_set,
+        }
+    }
+    /// gamma_G,sup = 1.35, gamma_G,inf = 1.0, gamma_Q = 1.5 and gamma_G,set = 1.2 for a
+    /// linear elastic analysis.
+    pub fn en1990() -> Self {
+        PartialFactors::new(1.35, 1.0, 1.5, 1.2)
+    }
+}
+
+/// A combination of load cases, each with its total factor.
+#[derive(Clone)]
+pub struct LoadCombination {
+    name: String,
+    factors: Vec<(usize, f64)>,
+}
+
+impl LoadCombination {
+    /// The combination of the load cases with the given indices and factors.
+    pub fn new(name: &str, factors: Vec<(usize, f64)>) -> Self {
+        LoadCombination {
+            name: name.to_string(),
+            factors,
+        }
+    }
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+    pub fn get_factors(&self) -> &[(usize, f64)] {
+        &self.factors
+    }
+
+    /// The superposed loading of the combination.
+    pub fn get_loading(&self, cases: &[LoadCase]) -> SystemLoading {
+        let mut res = SystemLoading::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        for &(c, f) in &self.factors {
+            res = res.add(&cases[c].get_loading().scale(f));
+        }
+        return res;
+    }
+
+    /// Generates all combinations of the design situation after EN 1990.
+    ///
+    /// Every variable action is the leading action once, every accompanying variable action is
+    /// either present or absent and every permanent action is either unfavourable or favourable.
+    /// The combinations of the permanent actions alone are included for favourable variable
+    /// actions. Duplicates are removed.
+    pub fn generate(
+        cases: &[LoadCase],
+        kind: CombinationType,
+        factors: &PartialFactors,
+    ) -> Vec<LoadCombination> {
+        let permanent: Vec<usize> = (0..cases.len())
+            .filter(|&i| cases[i].get_action().is_permanent())
+            .collect();
+        let variable: Vec<usize> = (0..cases.len())
+            .filter(|&i| !cases[i].get_action().is_permanent())
+            .collect();
+
+        // Die Varianten der ständigen Einwirkungen
+        let mut permanent_variants: Vec<Vec<(usize, f64)>> = vec![Vec::new()];
+        for &g in &permanent {
+            let options = match (kind, cases[g].get_action()) {
+                (CombinationType::Fundamental, ActionType::Settlement) => {
+                    vec![factors.gamma_g_set, 0.0]
+                }
+                (CombinationType::Fundamental, _) => vec![factors.gamma_g_sup, factors.gamma_g_inf],
+                (_, ActionType::Settlement) => vec![1.0, 0.0],
+                (_, _) => vec![1.0],
+            };
+            let mut next = Vec::new();
+            for v in &permanent_variants {
+                for &o in &options {
+                    let mut n = v.clone();
+                    n.push((g, o));
+                    next.push(n);
+                }
+            }
+            permanent_variants = next;
+        }
+
+        // Die Varianten der veränderlichen Einwirkungen, None ist die Kombination ohne Leiteinwirkung
+        let mut leading: Vec<Option<usize>> = vec![None];
+        if kind != CombinationType::QuasiPermanent {
+            leading.extend(variable.iter().map(|&q| Some(q)));
+        }
+
+        let mut res: Vec<LoadCombination> = Vec::new();
+        for lead in &leading {
+            let others: Vec<usize> = variable
+                .iter()
+                .cloned()
+                .filter(|&q| Some(q) != *lead)
+                .collect();
+            // Ohne Leiteinwirkung nur die ständigen Einwirkungen, außer quasi-ständig
+            let masks = if lead.is_none() && kind != CombinationType::QuasiPermanent {
+                1
+            } else {
+                1usize << others.len()
+            };
+            for mask in 0..masks {
+                for pv in &permanent_variants {
+                    let mut f = pv.clone();
+                    if let Some(q) = *lead {
+                        let psi = cases[q].get_action().get_psi();
+                        let factor = match kind {
+                            CombinationType::Fundamental => factors.gamma_q,
+                            CombinationType::Characteristic => 1.0,
+                            CombinationType::Frequent => psi[1],
+                            CombinationType::QuasiPermanent => psi[2],
+                        };
+                        f.push((q, factor));
+                    }
+                    for k in 0..others.len() {
+                        if mask & (1 << k) == 0 {
+                            continue;
+                        }
+                        let q = others[k];
+                        let psi = cases[q].get_action().get_psi();
+                        let factor = match kind {
+                            CombinationType::Fundamental => factors.gamma_q * psi[0],
+                            CombinationType::Characteristic => psi[0],
+                            CombinationType::Frequent | CombinationType::QuasiPermanent => psi[2],
+                        };
+                        f.push((q, factor));
+                    }
+                    f.retain(|&(_, factor)| factor != 0.0);
+                    f.sort_by(|a, b| a.0.cmp(&b.0));
+                    if res.iter().any(|c| c.factors == f) {
+                        continue;
+                    }
+                    let name = format!(
+                        "{:?} {}: {}",
+                        kind,
+                        res.len() + 1,
+                        f.iter()
+                            .map(|&(c, factor)| format!("{:.2}*{}", factor, cases[c].get_name()))
+                            .collect::<Vec<String>>()
+                            .join(" + ")
+                    );
+                    res.push(LoadCombination::new(&name, f));
+                }
+            }
+        }
+        return res;
+    }
+}
+
+impl System {
+    /// Solves the combination as a whole, so the second order results are not superposed.
+    pub fn solve_combination(
+        &self,
+        cases: &[LoadCase],
+        combination: &LoadCombination,
+        second_order: bool,
+    ) -> BeamResultSet {
+        let loading = combination.get_loading(cases);
+        if second_order {
+            self.matrix_stiffness_method_second_order(&loading)
+        } else {
+            self.matrix_stiffness_method_first_order(&loading)
+        }
+    }
+
+    /// Solves every combination, see `solve_combination`. With first order theory the stiffness
+    /// matrix is factorised only once.
+    pub fn solve_combinations(
+        &self,
+        cases: &[LoadCase],
+        combinations: &[LoadCombination],
+        second_order: bool,
+    ) -> Vec<BeamResultSet> {
+        if !second_order {
+            let prepared = self.prepare_first_order();
+            return combinations
+                .iter()
+                .map(|c| prepared.solve(&c.get_loading(cases)))
+                .collect();
+        }
+        combinations
+            .iter()
+            .map(|c| self.solve_combination(cases, c, second_order))
+            .collect()
+    }
+}
@@ -174,7 +174,7 @@ impl Beam {
 impl Support {
     pub(crate) fn stiffness_matrix(&self) -> Matrix3x3 {
         let m = Matrix3x3::new(
-            if self.get_free_dofs()[0] {
+            if self.has_spring(0) {
                 self.get_feder()[0]
             } else {
                 0.0
@@ -182,7 +182,7 @@ impl Support {
             0.0,
             0.0,
             0.0,
-            if self.get_free_dofs()[1] {
+            if self.has_spring(1) {
                 self.get_feder()[1]
             } else {
                 0.0
@@ -190,7 +190,7 @@ impl Support {
             0.0,
             0.0,
             0.0,
-            if self.get_free_dofs()[2] {
+            if self.has_spring(2) {
                 self.get_feder()[2]
             } else {
                 0.0
@@ -202,7 +202,209 @@ impl Support {
     }
 }
 
+impl Spring {
+    /// The stiffness matrix of the spring in the global coordinate system.
+    pub(crate) fn stiffness_matrix(&self) -> Matrix6x6 {
+        let mut m = Matrix6x6::zeros();
+        for i in 0..3 {
+            let k = self.get_stiffness()[i];
+            m[(i, i)] = k;
+            m[(i + 3, i + 3)] = k;
+            m[(i, i + 3)] = -k;
+            m[(i + 3, i)] = -k;
+        }
+        return transmatrix6x6(self.get_alpha()) * m * transmatrix6x6(self.get_alpha()).transpose();
+    }
+}
+
 impl System {
+    /// Transformation der Knotenverschiebungen auf die Anschnitte (starre Stabenden).
+    /// v_anschnitt = E * v_knoten, F_knoten = E^T * F_anschnitt
+    pub(crate) fn beam_offset_matrix(&self, beamindex: usize) -> Matrix6x6 {
+        let o = self.get_beam_global_offsets(beamindex);
+        let mut e = Matrix6x6::identity();
+        for k in 0..2 {
+            // Verschiebungen nach unten positiv, Verdrehung im Uhrzeigersinn
+            e[(3 * k, 3 * k + 2)] = o[k][1];
+            e[(3 * k + 1, 3 * k + 2)] = o[k][0];
+        }
+        return e;
+    }
+
+    /// Die Stabsteifigkeitsmatrix und der Lastvektor im globalen KOS, bezogen auf die Knoten.
+    pub(crate) fn beam_global_stiffness(
+        &self,
+        beamindex: usize,
+        stiff: Matrix6x6,
+        load_vec: Vector6,
+    ) -> (Matrix6x6, Vector6) {
+        let alpha = self.get_beam_alpha(beamindex);
+        let e = self.beam_offset_matrix(beamindex);
+        let f =
+            e.transpose() * transmatrix6x6(alpha) * stiff * transmatrix6x6(alpha).transpose() * e;
+        let lv = e.transpose() * transmatrix6x6(alpha) * load_vec;
+        return (f, lv);
+    }
+
+    /// Die lokalen Verschiebungen des Stabes an den Anschnitten aus der globalen Lösung.
+    pub(crate) fn beam_local_displacements(&self, beamindex: usize, result: &VectorD) -> Vector6 {
+        let from = self.get_beam_from_point(beamindex);
+        let to = self.get_beam_to_point(beamindex);
+        let alpha = self.get_beam_alpha(beamindex);
+
+        let mut v = Vector6::zeros();
+        for j in 0..3 {
+            v[j] = result[from * 3 + j];
+            v[j + 3] = result[to * 3 + j];
+        }
+        let v = self.beam_offset_matrix(beamindex) * v;
+        return transmatrix6x6(alpha).transpose() * v;
+    }
+
+    pub(crate) fn assemble_beam(
+        from: usize,
+        to: usize,
+        f: &Matrix6x6,
+        lv: &Vector6,
+        steif: &mut MatrixDxD,
+        last: &mut VectorD,
+    ) {
+        for i in 0..3 {
+            for j in 0..3 {
+                steif[(from * 3 + i, from * 3 + j)] =
+                    steif[(from * 3 + i, from * 3 + j)] + f[(i, j)];
+                steif[(from * 3 + i, to * 3 + j)] =
+                    steif[(from * 3 + i, to * 3 + j)] + f[(i, j + 3)];
+                steif[(to * 3 + i, from * 3 + j)] =
+                    steif[(to * 3 + i, from * 3 + j)] + f[(i + 3, j)];
+                steif[(to * 3 + i, to * 3 + j)] =
+                    steif[(to * 3 + i, to * 3 + j)] + f[(i + 3, j + 3)];
+            }
+            last[from * 3 + i] = last[from * 3 + i] - lv[i];
+            last[to * 3 + i] = last[to * 3 + i] - lv[i + 3];
+        }
+    }
+
+    /// The end forces of an active beam acting on its nodes in the global coordinate system.
+    pub(crate) fn beam_node_forces(&self, beamindex: usize, res: &BeamResult) -> Vector6 {
+        let rsk = res.get_rsks();
+        // Rückgängig machen der TM Definitionen
+        let f = Vector6::new(-rsk[0], -rsk[1], rsk[2], rsk[3], rsk[4], -rsk[5]);
+        let alpha = self.get_beam_alpha(beamindex);
+        return self.beam_offset_matrix(beamindex).transpose() * transmatrix6x6(alpha) * f;
+    }
+
+    /// The support reactions x_1, x_2, phi_3 in the global coordinate system, which the supports
+    /// exert on the points. Spring forces are included.
+    ///
+    /// Several supports may share a point, each gets the forces of its own fixed and spring dofs.
+    /// Panics if more than one of them holds the same dof.
+    pub fn support_reactions(&self, loading: &SystemLoading, res: &BeamResultSet) -> Vec<[f64; 3]> {
+        let mut nodes = VectorD::zeros(self.get_points().len() * 3);
+        for i in 0..self.get_beams().len() {
+            let f = self.beam_node_forces(i, &res.get_results()[i]);
+            let from = self.get_beam_from_point(i);
+            let to = self.get_beam_to_point(i);
+            for j in 0..3 {
+                nodes[from * 3 + j] = nodes[from * 3 + j] + f[j];
+                nodes[to * 3 + j] = nodes[to * 3 + j] + f[j + 3];
+            }
+        }
+        let mut u = VectorD::zeros(self.get_points().len() * 3);
+        for p in 0..res.get_node_displacements().len() {
+            for j in 0..3 {
+                u[p * 3 + j] = res.get_node_displacements()[p][j];
+            }
+        }
+        let mut springs = MatrixDxD::zeros(u.len(), u.len());
+        self.springs(&mut springs, &mut VectorD::zeros(u.len()));
+        let mut nodes = nodes + springs * &u;
+        for (c, lambda) in self
+            .get_constraints()
+            .iter()
+            .zip(res.get_constraint_forces())
+        {
+            for &(p, d, a) in c.get_terms() {
+                nodes[p * 3 + d] = nodes[p * 3 + d] - lambda * a;
+            }
+        }
+        let mut p = VectorD::zeros(self.get_points().len() * 3);
+        System::knotenlasten(loading, &mut p);
+        let r = nodes - p;
+
+        // Die Kräfte der Lagerfedern
+        let mut reactions = Vec::new();
+        for i in 0..self.get_supports().len() {
+            let sup = &self.get_supports()[i];
+            let sp = self.get_support_points()[i];
+            let settlement = loading.get_total_support_displacement(i);
+            let f = -(sup.stiffness_matrix() * u.fixed_rows::<3>(sp * 3));
+            let mut reaction = [f[0], f[1], f[2]];
+            for j in 0..3 {
+                if sup.has_spring(j) {
+                    let d = sup.get_contact_displacement(j) + settlement[j];
+                    reaction[j] = reaction[j] + sup.get_feder()[j] * d;
+                }
+            }
+            reactions.push(reaction);
+        }
+        // Der Rest der Reaktion am Punkt wirkt im festen Lager des Freiheitsgrades
+        for i in 0..self.get_supports().len() {
+            let sp = self.get_support_points()[i];
+            for j in 0..3 {
+                if !self.get_supports()[i].is_fixed(j) {
+                    continue;
+                }
+                let mut rest = r[sp * 3 + j];
+                for k in 0..self.get_supports().len() {
+                    if k != i && self.get_support_points()[k] == sp {
+                        if self.get_supports()[k].is_fixed(j) {
+                            panic!(
+                                "Mehrere Lager halten den Freiheitsgrad {} am Punkt {}.",
+                                j, sp
+                            );
+                        }
+                        rest = rest - reactions[k][j];
+                    }
+                }
+                reactions[i][j] = rest;
+            }
+        }
+        return reactions;
+    }
+
+    pub(super) fn springs(&self, steif: &mut MatrixDxD, last: &mut VectorD) {
+        for i in 0..self.get_springs().len() {
+            let [from, to] = self.get_spring_points()[i];
+            let f = self.get_springs()[i].stiffness_matrix();
+            System::assemble_beam(from, to, &f, &Vector6::zeros(), steif, last);
+        }
+    }
+
+    /// The forces [x1, x2, phi3] in the springs in their rotated coordinate systems, positive
+    /// when the spring is elongated.
+    pub fn spring_forces(&self, res: &BeamResultSet) -> Vec<[f64; 3]> {
+        let u = res.get_node_displacements();
+        let mut forces = Vec::new();
+        for i in 0..self.get_springs().len() {
+            let [from, to] = self.get_spring_points()[i];
+            let spring = &self.get_springs()[i];
+            let mut v = Vector6::zeros();
+            for j in 0..3 {
+                v[j] = u[from][j];
+                v[j + 3] = u[to][j];
+            }
+            let v = transmatrix6x6(spring.get_alpha()).transpose() * v;
+            let k = spring.get_stiffness();
+            forces.push([
+                k[0] * (v[3] - v[0]),
+                k[1] * (v[4] - v[1]),
+                k[2] * (v[5] - v[2]),
+            ]);
+        }
+        return forces;
+    }
+
     pub(super) fn knotenlasten(loading: &SystemLoading, last: &mut VectorD) {
         for i in 0..loading.get_static_loads().len() {
             let p = loading.get_static_load_points()[i];
@@ -214,8 +416,13 @@ impl System {
         }
     }
 
-    pub(super) fn supports(&self, dofs: usize, steif: &mut MatrixDxD, last: &mut VectorD) {
-        // Einarbeiten der Homogenen und TODO Inhomogenen Randbedingungen
+    pub(super) fn supports(
+        &self,
+        loading: &SystemLoading,
+        steif: &mut MatrixDxD,
+        last: &mut VectorD,
+    ) {
+        // Einarbeiten der Lagerfedern, die festen Lager werden in `solve_global` eliminiert
         for i in 0..self.get_supports().len() {
             let sup = &self.get_supports()[i];
             let sup_point = self.get_support_points()[i];
@@ -228,20 +435,147 @@ impl System {
                         steif[(sup_point * 3 + i, sup_point * 3 + j)] + m[(i, j)]
                 }
             }
+        }
+        self.support_loads(loading, last);
+    }
+
+    /// Die Lasten der Lagerfedern aus geschlossenen Klaffungen und Lagerverschiebungen.
+    pub(crate) fn support_loads(&self, loading: &SystemLoading, last: &mut VectorD) {
+        for i in 0..self.get_supports().len() {
+            let sup = &self.get_supports()[i];
+            let sup_point = self.get_support_points()[i];
 
+            let settlement = loading.get_total_support_displacement(i);
             for j in 0..3 {
-                if !sup.get_free_dofs()[j] {
-                    // TODO Gedrehte Supports
-                    // kein freier Knoten
+                // Geschlossene Klaffung einseitiger Lager und Lagerverschiebungen
+                let d = sup.get_contact_displacement(j) + settlement[j];
+                if sup.has_spring(j) {
+                    last[sup_point * 3 + j] = last[sup_point * 3 + j] + sup.get_feder()[j] * d;
+                }
+            }
+        }
+    }
+
+    /// Die Transformation u = T * u_red + u_0 der Knotenverschiebungen auf die unabhängigen
+    /// Freiheitsgrade. Feste Lager und abhängige Freiheitsgrade der Zwangsbedingungen werden
+    /// eliminiert.
+    pub(crate) fn dof_transformation(&self, loading: &SystemLoading) -> (MatrixDxD, VectorD) {
+        let dofs = self.get_points().len() * 3;
+        let mut t = MatrixDxD::identity(dofs, dofs);
+        let mut u0 = VectorD::zeros(dofs);
+        let mut eliminated = vec![false; dofs];
+
+        // Ersetzen des Freiheitsgrades s durch e * u_red + c in allen Zeilen
+        let substitute = |t: &mut MatrixDxD, u0: &mut VectorD, s: usize, e: &VectorD, c: f64| {
+            for r in 0..dofs {
+                let coef = t[(r, s)];
+                if coef != 0.0 {
                     for k in 0..dofs {
-                        steif[(k, sup_point * 3 + j)] = 0.0;
-                        steif[(sup_point * 3 + j, k)] = 0.0;
+                        t[(r, k)] = t[(r, k)] + coef * e[k];
                     }
-                    steif[(sup_point * 3 + j, sup_point * 3 + j)] = 1.0;
-                    last[sup_point * 3 + j] = 0.0;
+                    t[(r, s)] = t[(r, s)] - coef;
+                    u0[r] = u0[r] + coef * c;
+                }
+            }
+        };
+
+        // TODO Gedrehte Supports
+        for i in 0..self.get_supports().len() {
+            let sup = &self.get_supports()[i];
+            let sup_point = self.get_support_points()[i];
+            let settlement = loading.get_total_support_displacement(i);
+            for j in 0..3 {
+                let s = sup_point * 3 + j;
+                if sup.is_fixed(j) && !eliminated[s] {
+                    let d = sup.get_contact_displacement(j) + settlement[j];
+                    substitute(&mut t, &mut u0, s, &VectorD::zeros(dofs), d);
+                    eliminated[s] = true;
                 }
             }
         }
+
+        for c in self.get_constraints() {
+            let (sp, sd, sa) = c.get_terms()[0];
+            let s = sp * 3 + sd;
+            if eliminated[s] {
+                panic!("Abhängiger Freiheitsgrad {} ist bereits festgehalten.", s);
+            }
+            let mut e = VectorD::zeros(dofs);
+            let mut cst = 0.0;
+            for &(p, d, a) in &c.get_terms()[1..] {
+                let k = p * 3 + d;
+                e = e - (a / sa) * t.row(k).transpose();
+                cst = cst - (a / sa) * u0[k];
+            }
+            if e[s] != 0.0 {
+                panic!("Zirkuläre Zwangsbedingung am Freiheitsgrad {}.", s);
+            }
+            substitute(&mut t, &mut u0, s, &e, cst);
+            eliminated[s] = true;
+        }
+
+        let free: Vec<usize> = (0..dofs).filter(|&k| !eliminated[k]).collect();
+        let t = t.select_columns(&free);
+        return (t, u0);
+    }
+
+    /// Das reduzierte Gleichungssystem T^T K T u_red = T^T (f - K u_0).
+    pub(crate) fn reduced_system(
+        &self,
+        loading: &SystemLoading,
+        steif: &MatrixDxD,
+        last: &VectorD,
+    ) -> (MatrixDxD, VectorD, MatrixDxD, VectorD) {
+        let (t, u0) = self.dof_transformation(loading);
+        let k = t.transpose() * steif * &t;
+        let f = t.transpose() * (last - steif * &u0);
+        return (k, f, t, u0);
+    }
+
+    /// Löst das Gleichungssystem mit Lagern und Zwangsbedingungen. Gibt die globalen
+    /// Verschiebungen und die Zwangskräfte der Zwangsbedingungen zurück.
+    pub(crate) fn solve_global(
+        &self,
+        loading: &SystemLoading,
+        steif: &MatrixDxD,
+        last: &VectorD,
+    ) -> (VectorD, Vec<f64>) {
+        let (k, f, t, u0) = self.reduced_system(loading, steif, last);
+
+        let g = match k.cholesky() {
+            Some(t) => t,
+            None => panic!("Matrix nicht positiv definit."),
+        };
+        let result = t * g.solve(&f) + u0;
+        let forces = self.constraint_forces(&(steif * &result - last));
+        return (result, forces);
+    }
+
+    /// Die Zwangskräfte lambda aus dem Residuum r = K u - f an den abhängigen Freiheitsgraden.
+    pub(crate) fn constraint_forces(&self, residual: &VectorD) -> Vec<f64> {
+        let cons = self.get_constraints();
+        let m = cons.len();
+        if m == 0 {
+            return Vec::new();
+        }
+        let mut a = MatrixDxD::zeros(m, m);
+        let mut r = VectorD::zeros(m);
+        for i in 0..m {
+            let (sp, sd, _) = cons[i].get_terms()[0];
+            r[i] = residual[sp * 3 + sd];
+            for j in 0..m {
+                for &(p, d, coef) in cons[j].get_terms() {
+                    if p == sp && d == sd {
+                        a[(i, j)] = a[(i, j)] + coef;
+                    }
+                }
+            }
+        }
+        let lambda = match a.lu().solve(&r) {
+            Some(t) => t,
+            None => panic!("Zwangsbedingungen sind linear abhängig."),
+        };
+        return lambda.iter().cloned().collect();
     }
 
     pub fn matrix_stiffness_method_second_order(&self, loading: &SystemLoading) -> BeamResultSet {
@@ -258,70 +592,57 @@ impl System {
             let from = self.get_beam_from_point(i);
             let to = self.get_beam_to_point(i);
             let length = self.get_beam_lenght(i);
-            let alpha = self.get_beam_alpha(i);
-            let normal_component = 0.5 * (first_iter.res[i].rsk[0] + first_iter.res[i].rsk[3]);
 
             let lineloading = loading.get_total_lineload_for_beam(i);
 
             let b = &self.get_beams()[i];
+            if !b.is_active() {
+                // Nur die Volleinspannkräfte der Streckenlast
+                let loc = b.local_stiffness_and_load_first_order(length, Some(lineloading));
+                let (_, lv) = self.beam_global_stiffness(i, loc.0, loc.1);
+                System::assemble_beam(from, to, &Matrix6x6::zeros(), &lv, &mut steif, &mut last);
+                continue;
+            }
+            let normal_component = 0.5 * (first_iter.res[i].rsk[0] + first_iter.res[i].rsk[3]);
             let loc = b.local_stiffness_and_load_second_order(
                 length,
                 normal_component,
                 Some(lineloading),
             );
-            let stiff = loc.0;
-            let load_vec = loc.1;
-            let f = transmatrix6x6(alpha) * stiff * transmatrix6x6(alpha).transpose();
-            let lv = transmatrix6x6(alpha) * load_vec;
+            let (f, lv) = self.beam_global_stiffness(i, loc.0, loc.1);
             // Assemblierung der Globalen Stabsteifigkeitsmatrix
-            for i in 0..3 {
-                for j in 0..3 {
-                    steif[(from * 3 + i, from * 3 + j)] =
-                        steif[(from * 3 + i, from * 3 + j)] + f[(i, j)];
-                    steif[(from * 3 + i, to * 3 + j)] =
-                        steif[(from * 3 + i, to * 3 + j)] + f[(i, j + 3)];
-                    steif[(to * 3 + i, from * 3 + j)] =
-                        steif[(to * 3 + i, from * 3 + j)] + f[(i + 3, j)];
-                    steif[(to * 3 + i, to * 3 + j)] =
-                        steif[(to * 3 + i, to * 3 + j)] + f[(i + 3, j + 3)];
-                }
-                last[from * 3 + i] = last[from * 3 + i] - lv[i];
-                last[to * 3 + i] = last[to * 3 + i] - lv[i + 3];
-            }
+            System::assemble_beam(from, to, &f, &lv, &mut steif, &mut last);
         }
         // Einarbeiten der Knotenlasten
         System::knotenlasten(loading, &mut last);
 
-        self.supports(total_dofs, &mut steif, &mut last);
-
-        let g = match steif.cholesky() {
-            Some(t) => t,
-            None => panic!("Matrix nicht positiv definit."),
-        };
+        self.springs(&mut steif, &mut last);
+        self.supports(loading, &mut steif, &mut last);
 
         // Lösung in Globalen KOS
-        let result = g.solve(&last);
+        let (result, constraint_forces) = self.solve_global(loading, &steif, &last);
 
         // Lösung der Stäbe
         let mut r = Vec::new();
         for i in 0..self.get_beams().len() {
-            let from = self.get_beam_from_point(i);
-            let to = self.get_beam_to_point(i);
             let length = self.get_beam_lenght(i);
-            let alpha = self.get_beam_alpha(i);
 
-            let mut v = Vector6::zeros();
-            for j in 0..3 {
-                v[j] = result[from * 3 + j];
-                v[j + 3] = result[to * 3 + j];
-            }
+            let v = self.beam_local_displacements(i, &result);
             let lineloading = loading.get_total_lineload_for_beam(i);
+            if !self.get_beams()[i].is_active() {
+                r.push(BeamResult::new_inactive(
+                    &v,
+                    length,
+                    &self.get_beams()[i],
+                    lineloading,
+                ));
+                continue;
+            }
 
             let normal_component = 0.5 * (first_iter.res[i].rsk[0] + first_iter.res[i].rsk[3]);
 
             let b = &self.get_beams()[i];
 
-            let v = transmatrix6x6(alpha).transpose() * v;
             r.push(b.local_mech_boundary_forces_second_order(
                 length,
                 Some(lineloading),
@@ -330,7 +651,9 @@ impl System {
             ));
         }
 
-        return BeamResultSet::new(r);
+        return BeamResultSet::new(r)
+            .with_displacements(&result)
+            .with_constraint_forces(constraint_forces);
     }
 
     pub fn matrix_stiffness_method_second_order_matrix(
@@ -347,10 +670,12 @@ impl System {
 
         // Iterieren durch alle Stäbe
         for i in 0..self.get_beams().len() {
+            if !self.get_beams()[i].is_active() {
+                continue;
+            }
             let from = self.get_beam_from_point(i);
             let to = self.get_beam_to_point(i);
             let length = self.get_beam_lenght(i);
-            let alpha = self.get_beam_alpha(i);
             let normal_component = 0.5 * (first_iter.res[i].rsk[0] + first_iter.res[i].rsk[3]);
 
             let lineloading = loading.get_total_lineload_for_beam(i);
@@ -361,46 +686,58 @@ impl System {
                 normal_component,
                 Some(lineloading),
             );
-            let stiff = loc.0;
-            let load_vec = loc.1;
-            let f = transmatrix6x6(alpha) * stiff * transmatrix6x6(alpha).transpose();
-            let lv = transmatrix6x6(alpha) * load_vec;
+            let (f, lv) = self.beam_global_stiffness(i, loc.0, loc.1);
             // Assemblierung der Globalen Stabsteifigkeitsmatrix
-            for i in 0..3 {
-                for j in 0..3 {
-                    steif[(from * 3 + i, from * 3 + j)] =
-                        steif[(from * 3 + i, from * 3 + j)] + f[(i, j)];
-                    steif[(from * 3 + i, to * 3 + j)] =
-                        steif[(from * 3 + i, to * 3 + j)] + f[(i, j + 3)];
-                    steif[(to * 3 + i, from * 3 + j)] =
-                        steif[(to * 3 + i, from * 3 + j)] + f[(i + 3, j)];
-                    steif[(to * 3 + i, to * 3 + j)] =
-                        steif[(to * 3 + i, to * 3 + j)] + f[(i + 3, j + 3)];
-                }
-                last[from * 3 + i] = last[from * 3 + i] - lv[i];
-                last[to * 3 + i] = last[to * 3 + i] - lv[i + 3];
-            }
+            System::assemble_beam(from, to, &f, &lv, &mut steif, &mut last);
         }
         // Einarbeiten der Knotenlasten
         System::knotenlasten(loading, &mut last);
 
-        self.supports(total_dofs, &mut steif, &mut last);
+        self.springs(&mut steif, &mut last);
+        self.supports(loading, &mut steif, &mut last);
 
-        return steif;
+        // Matrix der unabhängigen Freiheitsgrade
+        return self.reduced_system(loading, &steif, &last).0;
     }
 }
 
 pub struct BeamResultSet {
     res: Vec<BeamResult>,
+    displacements: Vec<[f64; 3]>,
+    constraint_forces: Vec<f64>,
 }
 
 impl BeamResultSet {
     pub fn new(res: Vec<BeamResult>) -> Self {
-        BeamResultSet { res }
+        BeamResultSet {
+            res,
+            displacements: Vec::new(),
+            constraint_forces: Vec::new(),
+        }
+    }
+    pub(crate) fn with_constraint_forces(mut self, forces: Vec<f64>) -> Self {
+        self.constraint_forces = forces;
+        self
+    }
+    /// Attaches the global solution vector as node displacements.
+    pub(crate) fn with_displacements(mut self, result: &VectorD) -> Self {
+        self.displacements = (0..result.len() / 3)
+            .map(|p| [result[p * 3], result[p * 3 + 1], result[p * 3 + 2]])
+            .collect();
+        self
     }
     pub fn get_results(&self) -> &[BeamResult] {
         return &self.res;
     }
+    /// The displacements x_1, x_2, phi_3 of every point in the global coordinate system.
+    pub fn get_node_displacements(&self) -> &[[f64; 3]] {
+        return &self.displacements;
+    }
+    /// The force lambda of every constraint, the constraint exerts `+lambda * a_k` on the dof
+    /// with the coefficient `a_k`.
+    pub fn get_constraint_forces(&self) -> &[f64] {
+        return &self.constraint_forces;
+    }
 }
 
 pub struct BeamResult {
@@ -427,6 +764,23 @@ impl BeamResult {
             loading: loading.add(StaticLinearLineload::new_constant_load(0.0)),
         }
     }
+    /// The result of an inactive beam, which only carries its line load to the nodes.
+    pub(crate) fn new_inactive(
+        rv: &Vector6,
+        length: f64,
+        beam: &Beam,
+        lineload: StaticLinearLineload,
+    ) -> Self {
+        // Ohne Steifigkeit bleiben die Volleinspannkräfte der Streckenlast
+        let (_, f) = beam.local_stiffness_and_load_first_order(length, Some(lineload));
+        BeamResult::new(
+            &[-f[0], -f[1], f[2], f[3], f[4], -f[5]],
+            rv.as_slice(),
+            length,
+            beam,
+            &lineload,
+        )
+    }
     pub fn get_rsks(&self) -> &[f64; 6] {
         return &self.rsk;
     }
@@ -444,6 +798,5 @@ impl BeamResult {
     }
 }
 
-// TODO impl System result mit reduktionsmethode...
 // TODO hinzufügen von Einzellasten im Stab.
 // TODO hinzufügen von Einzellasten im Stab.
@@ -0,0 +1,216 @@
+use crate::stiffness::direct_stiffness::BeamResultSet;
+
+/// Internal forces and displacements [N, Q, M, u, w, phi] along the beams, which can be drawn.
+pub trait InternalForceField {
+    fn get_beam_count(&self) -> usize;
+    fn get_beam_lenght(&self, beamindex: usize) -> f64;
+    fn get_internals_at(&self, beamindex: usize, x: f64) -> [f64; 6];
+}
+
+impl InternalForceField for BeamResultSet {
+    fn get_beam_count(&self) -> usize {
+        self.get_results().len()
+    }
+    fn get_beam_lenght(&self, beamindex: usize) -> f64 {
+        self.get_results()[beamindex].get_beam_lenght()
+    }
+    fn get_internals_at(&self, beamindex: usize, x: f64) -> [f64; 6] {
+        self.get_results()[beamindex].get_internals_at(x)
+    }
+}
+
+/// The sampled envelope of one beam.
+pub struct BeamEnvelope {
+    positions: Vec<f64>,
+    min: Vec<[f64; 6]>,
+    max: Vec<[f64; 6]>,
+    min_combination: Vec<[usize; 6]>,
+    max_combination: Vec<[usize; 6]>,
+}
+
+impl BeamEnvelope {
+    pub(crate) fn new(
+        positions: Vec<f64>,
+        min: Vec<[f64; 6]>,
+        max: Vec<[f64; 6]>,
+        min_combination: Vec<[usize; 6]>,
+        max_combination: Vec<[usize; 6]>,
+    ) -> Self {
+        BeamEnvelope {
+            positions,
+            min,
+            max,
+            min_combination,
+            max_combination,
+        }
+    }
+    pub fn get_positions(&self) -> &[f64] {
+        &self.positions
+    }
+    pub fn get_min(&self) -> &[[f64; 6]] {
+        &self.min
+    }
+    pub fn get_max(&self) -> &[[f64; 6]] {
+        &self.max
+    }
+    /// The index of the governing combination of every minimum.
+    pub fn get_min_combination(&self) -> &[[usize; 6]] {
+        &self.min_combination
+    }
+    /// The index of the governing combination of every maximum.
+    pub fn get_max_combination(&self) -> &[[usize; 6]] {
+        &self.max_combination
+    }
+    fn interpolate(&self, values: &[[f64; 6]], x: f64) -> [f64; 6] {
+        let n = self.positions.len();
+        let mut i = 0;
+        while i + 2 < n && self.positions[i + 1] < x {
+            i += 1;
+        }
+        let (x0, x1) = (self.positions[i], self.positions[i + 1]);
+        let t = ((x - x0) / (x1 - x0)).clamp(0.0, 1.0);
+        let mut res = [0.0; 6];
+        for k in 0..6 {
+            res[k] = values[i][k] * (1.0 - t) + values[i + 1][k] * t;
+        }
+        return res;
+    }
+}
+
+/// Min/max envelopes of the internal forces and displacements [N, Q, M, u, w, phi] over many
+/// results, e.g. the load combinations, together with the governing result.
+pub struct Envelope {
+    names: Vec<String>,
+    beams: Vec<BeamEnvelope>,
+}
+
+impl Envelope {
+    /// Samples every beam at `samples + 1` equidistant points with `BeamResult::get_internals_at`.
+    ///
+    /// Panics if there are no results or `samples` is zero.
+    pub fn new(results: &[BeamResultSet], names: Vec<String>, samples: usize) -> Self {
+        if results.is_empty() {
+            panic!("Die Umhüllende braucht mindestens ein Ergebnis.");
+        }
+        if samples == 0 {
+            panic!("Die Umhüllende braucht mindestens einen Abschnitt je Stab.");
+        }
+        let mut beams = Vec::new();
+        for b in 0..results[0].get_results().len() {
+            let l = results[0].get_results()[b].get_beam_lenght();
+            let positions: Vec<f64> = (0..=samples)
+                .map(|s| l * s as f64 / samples as f64)
+                .collect();
+            let mut min = vec![[f64::INFINITY; 6]; positions.len()];
+            let mut max = vec![[f64::NEG_INFINITY; 6]; positions.len()];
+            let mut min_combination = vec![[0; 6]; positions.len()];
+            let mut max_combination = vec![[0; 6]; positions.len()];
+            for c in 0..results.len() {
+                let r = &results[c].get_results()[b];
+                for s in 0..positions.len() {
+                    let v = r.get_internals_at(positions[s]);
+                    for k in 0..6 {
+                        if v[k] < min[s][k] {
+                            min[s][k] = v[k];
+                            min_combination[s][k] = c;
+                        }
+                        if v[k] > max[s][k] {
+                            max[s][k] = v[k];
+                            max_combination[s][k] = c;
+                        }
+                    }
+                }
+            }
+            beams.push(BeamEnvelope::new(
+                positions,
+                min,
+                max,
+                min_combination,
+                max_combination,
+            ));
+        }
+        Envelope { names, beams }
+    }
+    pub(crate) fn from_beams(names: Vec<String>, beams: Vec<BeamEnvelope>) -> Self {
+        Envelope { names, beams }
+    }
+    pub fn get_beams(&self) -> &[BeamEnvelope] {
+        &self.beams
+    }
+    pub fn get_names(&self) -> &[String] {
+        &self.names
+    }
+    /// The upper bound as a drawable field.
+    pub fn get_max_field(&self) -> EnvelopeBound<'_> {
+        EnvelopeBound {
+            envelope: self,
+            upper: true,
+        }
+    }
+    /// The lower bound as a drawable field.
+    pub fn get_min_field(&self) -> EnvelopeBound<'_> {
+        EnvelopeBound {
+            envelope: self,
+            upper: false,
+        }
+    }
+
+    /// One line per beam and sample with min, governing combination, max, governing combination
+    /// for N, Q, M, u, w, phi.
+    pub fn to_csv(&self) -> String {
+        let mut s = String::from("beam,x");
+        for c in ["N", "Q", "M", "u", "w", "phi"] {
+            s.push_str(&format!(
+                ",{0}_min,{0}_min_combination,{0}_max,{0}_max_combination",
+                c
+            ));
+        }
+        s.push('\n');
+        for b in 0..self.beams.len() {
+            let e = &self.beams[b];
+            for i in 0..e.positions.len() {
+                s.push_str(&format!("{},{}", b, e.positions[i]));
+                for k in 0..6 {
+                    s.push_str(&format!(
+                        ",{},{},{},{}",
+                        e.min[i][k],
+                        self.name(e.min_combination[i][k]),
+                        e.max[i][k],
+                        self.name(e.max_combination[i][k])
+                    ));
+                }
+                s.push('\n');
+            }
+        }
+        return s;
+    }
+    fn name(&self, combination: usize) -> String {
+        match self.names.get(combination) {
+            Some(n) => n.clone(),
+            None => combination.to_string(),
+        }
+    }
+}
+
+/// The upper or lower bound of an envelope, linear between the samples.
+pub struct EnvelopeBound<'a> {
+    envelope: &'a Envelope,
+    upper: bool,
+}
+
+impl<'a> InternalForceField for EnvelopeBound<'a> {
+    fn get_beam_count(&self) -> usize {
+        self.envelope.beams.len()
+    }
+    fn get_beam_lenght(&self, beamindex: usize) -> f64 {
+        *self.envelope.beams[beamindex].positions.last().unwrap()
+    }
+    fn get_internals_at(&self, beamindex: usize, x: f64) -> [f64; 6] {
+        let e = &self.envelope.beams[beamindex];
+        if self.upper {
+            e.interpolate(&e.max, x)
+        } else {
+            e.interpolate(&e.min, x)
+        }
+    }
+}
@@ -0,0 +1,384 @@
+use crate::stiffness::direct_stiffness::BeamResultSet;
+use crate::stiffness::system::*;
+
+/// The type of the generated system.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ExerciseKind {
+    /// A beam over several spans, clamped at the ends for a higher indeterminacy.
+    ContinuousBeam,
+    /// A three-hinged frame, which becomes a two-hinged or clamped frame for a higher
+    /// indeterminacy (at most 3).
+    ThreeHingedFrame,
+    /// A Pratt truss, additional diagonals and a second pin support raise the indeterminacy (at
+    /// most the number of panels plus one).
+    Truss,
+}
+
+/// Ein einfacher Zufallszahlengenerator (SplitMix64), damit die Aufgaben mit dem Startwert
+/// reproduzierbar sind.
+struct Random {
+    state: u64,
+}
+
+impl Random {
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+    /// Eine ganze Zahl aus [from, to].
+    fn between(&mut self, from: i64, to: i64) -> f64 {
+        (from + (self.next() % (to - from + 1) as u64) as i64) as f64
+    }
+    fn chance(&mut self, p: f64) -> bool {
+        ((self.next() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+}
+
+/// A generated exercise with the answer key.
+pub struct Exercise {
+    kind: ExerciseKind,
+    system: System,
+    loading: SystemLoading,
+    degree: usize,
+    results: BeamResultSet,
+    reactions: Vec<[f64; 3]>,
+}
+
+impl Exercise {
+    pub fn get_kind(&self) -> ExerciseKind {
+        self.kind
+    }
+    pub fn get_system(&self) -> &System {
+        &self.system
+    }
+    pub fn get_loading(&self) -> &SystemLoading {
+        &self.loading
+    }
+    /// The degree of static indeterminacy of the generated system.
+    pub fn get_indeterminacy(&self) -> usize {
+        self.degree
+    }
+    /// The results after first order theory.
+    pub fn get_results(&self) -> &BeamResultSet {
+        &self.results
+    }
+    /// The support reactions x_1, x_2, phi_3 in the global coordinate system.
+    pub fn get_support_reactions(&self) -> &[[f64; 3]] {
+        &self.reactions
+    }
+    /// The end forces [N, Q, M] at the start and at the end of every beam.
+    pub fn get_end_forces(&self) -> Vec<[f64; 6]> {
+        self.results
+            .get_results()
+            .iter()
+            .map(|r| *r.get_rsks())
+            .collect()
+    }
+}
+
+/// Generates random exercises with round dimensions and loads, which are reproducible with the
+/// seed.
+pub struct ExerciseGenerator {
+    random: Random,
+    difficulty: usize,
+    indeterminacy: usize,
+}
+
+impl ExerciseGenerator {
+    /// An easy and statically determinate generator.
+    pub fn new(seed: u64) -> Self {
+        ExerciseGenerator {
+            random: Random { state: seed },
+            difficulty: 1,
+            indeterminacy: 0,
+        }
+    }
+    /// The difficulty from 1 to 3 sets the size of the systems and the variety of the loads.
+    pub fn with_difficulty(mut self, difficulty: usize) -> Self {
+        self.difficulty = difficulty.clamp(1, 3);
+        self
+    }
+    /// The requested degree of static indeterminacy, as far as the kind allows it.
+    pub fn with_indeterminacy(mut self, indeterminacy: usize) -> Self {
+        self.indeterminacy = indeterminacy;
+        self
+    }
+
+    /// Generates the next exercise and solves it with the stiffness method.
+    ///
+    /// The degree of indeterminacy is determined with the force method, which also rejects
+    /// kinematic systems.
+    pub fn generate(&mut self, kind: ExerciseKind) -> Exercise {
+        let (system, loading) = match kind {
+            ExerciseKind::ContinuousBeam => self.continuous_beam(),
+            ExerciseKind::ThreeHingedFrame => self.frame(),
+            ExerciseKind::Truss => self.truss(),
+        };
+        let degree = system.force_method(&loading).get_degree();
+        let results = system.matrix_stiffness_method_first_order(&loading);
+        let reactions = system.support_reactions(&loading, &results);
+        Exercise {
+            kind,
+            system,
+            loading,
+            degree,
+            results,
+            reactions,
+        }
+    }
+
+    fn crosssection() -> Crosssection {
+        // IPE 300
+        Crosssection::new(2.1e8, 5.38e-3, 8.356e-5)
+    }
+
+    fn continuous_beam(&mut self) -> (System, SystemLoading) {
+        let n = self.indeterminacy;
+        let spans = (self.difficulty + 1)
+            .min(n + 1)
+            .max(n.saturating_sub(1))
+            .max(1);
+        // Die restliche Unbestimmtheit kommt aus Einspannungen an den Enden
+        let mut clamps = [false; 2];
+        for _ in 0..n + 1 - spans {
+            if !clamps[0] && (clamps[1] || self.random.chance(0.5)) {
+                clamps[0] = true;
+            } else {
+                clamps[1] = true;
+            }
+        }
+
+        let beam = Beam::new(Self::crosssection(), [false; 6], [0.0; 6], 0.0, 0.0);
+        let mut points = vec![Point::new(0.0, 0.0)];
+        let mut support_points = vec![0];
+        let mut beam_points = Vec::new();
+        let mut loaded_points = Vec::new();
+        let mut loads = Vec::new();
+        let mut loaded_beams = Vec::new();
+        let mut lineloads = Vec::new();
+        let mut x = 0.0;
+        for _ in 0..spans {
+            let l = self.random.between(3, 8);
+            let start = points.len() - 1;
+            let lineload = if self.random.chance(0.7) {
+                let q = 5.0 * self.random.between(1, 6);
+                if self.difficulty == 3 && self.random.chance(0.5) {
+                    Some(StaticLinearLineload::new_linear_load(
+                        q,
+                        5.0 * self.random.between(0, 6),
+                    ))
+                } else {
+                    Some(StaticLinearLineload::new_constant_load(q))
+                }
+            } else {
+                None
+            };
+            // Einzellast in Feldmitte mit zusätzlichem Punkt
+            if self.difficulty >= 2 && self.random.chance(0.5) {
+                points.push(Point::new(x + l / 2.0, 0.0));
+                loaded_points.push(points.len() - 1);
+                loads.push(StaticLoad::new(0.0, 10.0 * self.random.between(1, 5), 0.0));
+                beam_points.push([start, points.len() - 1]);
+                if let Some(q) = lineload {
+                    // Die lineare Last wird in der Feldmitte geteilt
+                    let m = 0.5 * (q.get_from_perpendicular_load() + q.get_to_perpendicular_load());
+                    loaded_beams.push(beam_points.len() - 1);
+                    lineloads.push(StaticLinearLineload::new_linear_load(
+                        q.get_from_perpendicular_load(),
+                        m,
+                    ));
+                    loaded_beams.push(beam_points.len());
+                    lineloads.push(StaticLinearLineload::new_linear_load(
+                        m,
+                        q.get_to_perpendicular_load(),
+                    ));
+                }
+                points.push(Point::new(x + l, 0.0));
+                beam_points.push([points.len() - 2, points.len() - 1]);
+            } else {
+                points.push(Point::new(x + l, 0.0));
+                beam_points.push([start, points.len() - 1]);
+                if let Some(q) = lineload {
+                    loaded_beams.push(beam_points.len() - 1);
+                    lineloads.push(q);
+                }
+            }
+            support_points.push(points.len() - 1);
+            x += l;
+        }
+        if loaded_points.is_empty() && loaded_beams.is_empty() {
+            loaded_beams.push(0);
+            lineloads.push(StaticLinearLineload::new_constant_load(10.0));
+        }
+
+        let mut supports = Vec::new();
+        for i in 0..support_points.len() {
+            let free = if i == 0 {
+                [false, false, !clamps[0]]
+            } else if i == support_points.len() - 1 && clamps[1] {
+                [true, false, false]
+            } else {
+                [true, false, true]
+            };
+            supports.push(Support::new(0.0, free, [0.0; 3]));
+        }
+        let beams = vec![beam; beam_points.len()];
+        let system = System::new(points, beam_points, beams, support_points, supports);
+        let loading = SystemLoading::new(loaded_points, loads, loaded_beams, lineloads);
+        return (system, loading);
+    }
+
+    fn frame(&mut self) -> (System, SystemLoading) {
+        let n = self.indeterminacy.min(3);
+        let h1 = self.random.between(3, 6);
+        let h2 = if self.difficulty == 3 {
+            self.random.between(3, 6)
+        } else {
+            h1
+        };
+        let w = 2.0 * self.random.between(2, 5);
+        let rise = if self.difficulty >= 2 {
+            self.random.between(1, 2)
+        } else {
+            0.0
+        };
+
+        let points = vec![
+            Point::new(0.0, 0.0),
+            Point::new(0.0, h1),
+            Point::new(w / 2.0, h1.max(h2) + rise),
+            Point::new(w, h2),
+            Point::new(w, 0.0),
+        ];
+        let cs = Self::crosssection();
+        let beam = Beam::new(cs, [false; 6], [0.0; 6], 0.0, 0.0);
+        let hinge = if n == 0 {
+            Beam::new(
+                cs,
+                [false, false, false, false, false, true],
+                [0.0; 6],
+                0.0,
+                0.0,
+            )
+        } else {
+            beam
+        };
+        let supports = vec![
+            Support::new(0.0, [false, false, n < 2], [0.0; 3]),
+            Support::new(0.0, [false, false, n < 3], [0.0; 3]),
+        ];
+        let system = System::new(
+            points,
+            vec![[0, 1], [1, 2], [2, 3], [4, 3]],
+            vec![beam, hinge, beam, beam],
+            vec![0, 4],
+            supports,
+        );
+
+        let mut loaded_beams = vec![1, 2];
+        let q = 5.0 * self.random.between(1, 4);
+        let mut lineloads = vec![
+            StaticLinearLineload::new_constant_load(q),
+            StaticLinearLineload::new_constant_load(q),
+        ];
+        if self.difficulty >= 2 {
+            // Wind auf die linke Stütze
+            loaded_beams.push(0);
+            lineloads.push(StaticLinearLineload::new_constant_load(
+                self.random.between(1, 4),
+            ));
+        }
+        let loading = SystemLoading::new(
+            vec![1],
+            vec![StaticLoad::new(10.0 * self.random.between(1, 3), 0.0, 0.0)],
+            loaded_beams,
+            lineloads,
+        );
+        return (system, loading);
+    }
+
+    fn truss(&mut self) -> (System, SystemLoading) {
+        let panels = self.difficulty + 1;
+        let n = self.indeterminacy.min(panels + 1);
+        let a = self.random.between(2, 4);
+        let h = self.random.between(2, 4);
+
+        // Untergurt 0..=panels, Obergurt panels+1..
+        let mut points = Vec::new();
+        for y in [0.0, h] {
+            for i in 0..=panels {
+                points.push(Point::new(i as f64 * a, y));
+            }
+        }
+        let top = |i: usize| panels + 1 + i;
+        let mut bars = Vec::new();
+        for i in 0..panels {
+            bars.push([i, i + 1]);
+            bars.push([top(i), top(i + 1)]);
+        }
+        for i in 0..=panels {
+            bars.push([i, top(i)]);
+        }
+        // Pratt-Fachwerk, die Diagonalen fallen zur Mitte
+        for i in 0..panels {
+            if 2 * i < panels {
+                bars.push([top(i), i + 1]);
+            } else {
+                bars.push([i, top(i + 1)]);
+            }
+        }
+        let second_pin = n > panels;
+        // Gekreuzte Diagonalen in zufälligen Feldern
+        let mut order: Vec<usize> = (0..panels).collect();
+        for i in (1..panels).rev() {
+            let j = self.random.between(0, i as i64) as usize;
+            order.swap(i, j);
+        }
+        for &i in &order[..n - second_pin as usize] {
+            if 2 * i < panels {
+                bars.push([i, top(i + 1)]);
+            } else {
+                bars.push([top(i), i + 1]);
+            }
+        }
+
+        // Gelenkige Anschlüsse, je Knoten ist ein Stab biegesteif angeschlossen
+        let cs = Self::crosssection();
+        let mut rigid = vec![false; points.len()];
+        let mut beams = Vec::new();
+        for bar in &bars {
+            let mut dofs = [false; 6];
+            for k in 0..2 {
+                if rigid[bar[k]] {
+                    dofs[3 * k + 2] = true;
+                } else {
+                    rigid[bar[k]] = true;
+                }
+            }
+            beams.push(Beam::new(cs, dofs, [0.0; 6], 0.0, 0.0));
+        }
+        let supports = vec![
+            Support::new(0.0, [false, false, true], [0.0; 3]),
+            Support::new(0.0, [!second_pin, false, true], [0.0; 3]),
+        ];
+        let system = System::new(points, bars, beams, vec![0, panels], supports);
+
+        let mut loaded_points = Vec::new();
+        let mut loads = Vec::new();
+        for i in 1..panels {
+            if self.random.chance(0.7) || (i == panels - 1 && loads.is_empty()) {
+                loaded_points.push(i);
+                loads.push(StaticLoad::new(0.0, 10.0 * self.random.between(1, 5), 0.0));
+            }
+        }
+        if self.difficulty >= 2 {
+            loaded_points.push(top(0));
+            loads.push(StaticLoad::new(10.0 * self.random.between(1, 3), 0.0, 0.0));
+        }
+        let loading = SystemLoading::new(loaded_points, loads, Vec::new(), Vec::new());
+        return (system, loading);
+    }
+}
@@ -41,7 +41,7 @@ impl Beam {
         return r;
     }
 
-    fn local_stiffness_and_load_first_order(
+    pub(crate) fn local_stiffness_and_load_first_order(
         &self,
         lenght: f64,
         lineload: Option<StaticLinearLineload>,
@@ -115,7 +115,7 @@ impl Beam {
 }
 
 impl System {
-    fn stiffness_matrix_first_order(
+    pub(crate) fn stiffness_matrix_first_order(
         &self,
         loading: &SystemLoading,
         steif: &mut MatrixDxD,
@@ -125,31 +125,16 @@ impl System {
             let from = self.get_beam_from_point(i);
             let to = self.get_beam_to_point(i);
             let length = self.get_beam_lenght(i);
-            let alpha = self.get_beam_alpha(i);
 
             let lineloading = loading.get_total_lineload_for_beam(i);
 
             let b = &self.get_beams()[i];
             let loc = b.local_stiffness_and_load_first_order(length, Some(lineloading));
-            let stiff = loc.0;
-            let load_vec = loc.1;
-            let f = transmatrix6x6(alpha) * stiff * transmatrix6x6(alpha).transpose();
-            let lv = transmatrix6x6(alpha) * load_vec;
+            let (f, lv) = self.beam_global_stiffness(i, loc.0, loc.1);
+            // Ausgefallene Stäbe geben nur die Volleinspannkräfte ihrer Streckenlast ab
+            let f = if b.is_active() { f } else { Matrix6x6::zeros() };
             // Assemblierung der Globalen Stabsteifigkeitsmatrix
-            for i in 0..3 {
-                for j in 0..3 {
-                    steif[(from * 3 + i, from * 3 + j)] =
-                        steif[(from * 3 + i, from * 3 + j)] + f[(i, j)];
-                    steif[(from * 3 + i, to * 3 + j)] =
-                        steif[(from * 3 + i, to * 3 + j)] + f[(i, j + 3)];
-                    steif[(to * 3 + i, from * 3 + j)] =
-                        steif[(to * 3 + i, from * 3 + j)] + f[(i + 3, j)];
-                    steif[(to * 3 + i, to * 3 + j)] =
-                        steif[(to * 3 + i, to * 3 + j)] + f[(i + 3, j + 3)];
-                }
-                last[from * 3 + i] = last[from * 3 + i] - lv[i];
-                last[to * 3 + i] = last[to * 3 + i] - lv[i + 3];
-            }
+            System::assemble_beam(from, to, &f, &lv, steif, last);
         }
     }
 
@@ -166,38 +151,82 @@ impl System {
         // Einarbeiten der Knotenlasten
         System::knotenlasten(loading, &mut last);
 
-        self.supports(total_dofs, &mut steif, &mut last);
-
-        let g = match steif.cholesky() {
-            Some(t) => t,
-            None => panic!("Matrix nicht positiv definit."),
-        };
+        self.springs(&mut steif, &mut last);
+        self.supports(loading, &mut steif, &mut last);
 
         // Lösung in Globalen KOS
-        let result = g.solve(&last);
+        let (result, constraint_forces) = self.solve_global(loading, &steif, &last);
 
-        // Lösung der Stäbe
-        let mut r = Vec::new();
+        return self
+            .beam_results_first_order(loading, &result)
+            .with_constraint_forces(constraint_forces);
+    }
+
+    /// Die globale Steifigkeitsmatrix mit Federn und Lagerfedern, unabhängig von der Belastung.
+    pub(crate) fn global_stiffness_first_order(&self) -> MatrixDxD {
+        let empty = SystemLoading::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        let total_dofs = self.get_points().len() * 3;
+        let mut steif = MatrixDxD::zeros(total_dofs, total_dofs);
+        let mut last = VectorD::zeros(total_dofs);
+
+        self.stiffness_matrix_first_order(&empty, &mut steif, &mut last);
+        self.springs(&mut steif, &mut last);
+        self.supports(&empty, &mut steif, &mut last);
+        return steif;
+    }
+
+    /// Der globale Lastvektor ohne Aufbau der Steifigkeitsmatrix.
+    pub(crate) fn load_vector_first_order(&self, loading: &SystemLoading) -> VectorD {
+        let mut last = VectorD::zeros(self.get_points().len() * 3);
         for i in 0..self.get_beams().len() {
             let from = self.get_beam_from_point(i);
             let to = self.get_beam_to_point(i);
             let length = self.get_beam_lenght(i);
-            let alpha = self.get_beam_alpha(i);
 
-            let mut v = Vector6::zeros();
+            let lineloading = loading.get_total_lineload_for_beam(i);
+
+            let b = &self.get_beams()[i];
+            let loc = b.local_stiffness_and_load_first_order(length, Some(lineloading));
+            let (_, lv) = self.beam_global_stiffness(i, loc.0, loc.1);
             for j in 0..3 {
-                v[j] = result[from * 3 + j];
-                v[j + 3] = result[to * 3 + j];
+                last[from * 3 + j] = last[from * 3 + j] - lv[j];
+                last[to * 3 + j] = last[to * 3 + j] - lv[j + 3];
             }
+        }
+        System::knotenlasten(loading, &mut last);
+        self.support_loads(loading, &mut last);
+        return last;
+    }
+
+    /// Die Schnittgrößen der Stäbe aus den globalen Verschiebungen.
+    pub(crate) fn beam_results_first_order(
+        &self,
+        loading: &SystemLoading,
+        result: &VectorD,
+    ) -> BeamResultSet {
+        // Lösung der Stäbe
+        let mut r = Vec::new();
+        for i in 0..self.get_beams().len() {
+            let length = self.get_beam_lenght(i);
+
+            let v = self.beam_local_displacements(i, result);
             let lineloading = loading.get_total_lineload_for_beam(i);
+            if !self.get_beams()[i].is_active() {
+                r.push(BeamResult::new_inactive(
+                    &v,
+                    length,
+                    &self.get_beams()[i],
+                    lineloading,
+                ));
+                continue;
+            }
 
             let b = &self.get_beams()[i];
 
-            let v = transmatrix6x6(alpha).transpose() * v;
             r.push(b.local_mech_boundary_forces_first_order(length, Some(lineloading), v));
         }
 
-        return BeamResultSet::new(r);
+        return BeamResultSet::new(r).with_displacements(result);
     }
 }
 
@@ -221,7 +250,7 @@ impl BeamResult {
         let v = mat * v;
         return [v[5], v[4], v[3], v[0], v[1], v[2]];
     }
-    fn lastvektor_perpendicular_first_order(&self, start_l: f64, end_l: f64) -> Vector7 {
+    pub(crate) fn lastvektor_perpendicular_first_order(&self, start_l: f64, end_l: f64) -> Vector7 {
         if start_l == 0.0 && end_l == 0.0 {
             return Vector7::from_row_slice(&[0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0]);
         }
@@ -246,7 +275,7 @@ impl BeamResult {
         ]);
         return v;
     }
-    fn uebertragungsmatrix_first_order(&self, dlength: f64) -> Matrix7x7 {
+    pub(crate) fn uebertragungsmatrix_first_order(&self, dlength: f64) -> Matrix7x7 {
         let length = dlength;
         let b = self.get_beam();
         let ei = b.get_emodul() * b.get_ftm();
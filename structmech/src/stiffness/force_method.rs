@@ -0,0 +1,372 @@
+use crate::stiffness::direct_stiffness::{transmatrix6x6, BeamResult, BeamResultSet};
+use crate::stiffness::system::*;
+use nalgebra::DVector;
+use nalgebra::Dynamic;
+use nalgebra::OMatrix;
+use nalgebra::SVector;
+
+type MatrixDxD = OMatrix<f64, Dynamic, Dynamic>;
+type VectorD = DVector<f64>;
+type Vector6 = SVector<f64, 6>;
+
+/// A force of the force method.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ForceUnknown {
+    /// The normal force (`force = 0`), the moment at the start (`force = 1`) or at the end
+    /// (`force = 2`) of the beam.
+    Beam { beam: usize, force: usize },
+    /// The force of the spring in the direction `dof` of its rotated coordinate system.
+    Spring { spring: usize, dof: usize },
+    /// The reaction of the support in the global direction `dof`.
+    Support { support: usize, dof: usize },
+}
+
+pub struct ForceMethodResultSet {
+    unknowns: Vec<ForceUnknown>,
+    redundants: Vec<usize>,
+    flexibility: MatrixDxD,
+    load_terms: VectorD,
+    values: VectorD,
+    basic_state: BeamResultSet,
+    unit_states: Vec<BeamResultSet>,
+    results: BeamResultSet,
+}
+
+impl ForceMethodResultSet {
+    /// The degree of static indeterminacy.
+    pub fn get_degree(&self) -> usize {
+        self.redundants.len()
+    }
+    /// All forces of the system, whose equilibrium is formulated.
+    pub fn get_unknowns(&self) -> &[ForceUnknown] {
+        &self.unknowns
+    }
+    /// The chosen redundants X_i.
+    pub fn get_redundants(&self) -> Vec<ForceUnknown> {
+        self.redundants.iter().map(|&i| self.unknowns[i]).collect()
+    }
+    /// The flexibility matrix delta_ik.
+    pub fn get_flexibility(&self) -> &MatrixDxD {
+        &self.flexibility
+    }
+    /// The load terms delta_i0, including the support displacements.
+    pub fn get_load_terms(&self) -> &VectorD {
+        &self.load_terms
+    }
+    /// The values of the redundants from `delta X + delta_0 = 0`.
+    pub fn get_redundant_values(&self) -> &VectorD {
+        &self.values
+    }
+    /// The internal forces of the statically determinate basic system under the loading.
+    pub fn get_basic_state(&self) -> &BeamResultSet {
+        &self.basic_state
+    }
+    /// The internal forces of the basic system for `X_i = 1` without loading.
+    pub fn get_unit_states(&self) -> &[BeamResultSet] {
+        &self.unit_states
+    }
+    /// The results of the system, the same as of the displacement method.
+    pub fn get_results(&self) -> &BeamResultSet {
+        &self.results
+    }
+}
+
+impl System {
+    /// Solves the system with the force method (Kraftgrößenverfahren) using first order theory.
+    ///
+    /// The redundants are chosen by Gauss-Jordan elimination of the equilibrium matrix of the
+    /// points, support reactions are preferred. The flexibility matrix follows from the
+    /// principle of virtual forces with bending, normal force and springs, the displacements of
+    /// the points from the reduction theorem. Only moment releases are allowed and constraints are
+    /// not supported. Like in the stiffness method the fixed dofs of rotated supports act in the
+    /// global directions, springs of rotated supports are not supported.
+    pub fn force_method(&self, loading: &SystemLoading) -> ForceMethodResultSet {
+        if !self.get_constraints().is_empty() {
+            panic!("Zwangsbedingungen werden vom Kraftgrößenverfahren nicht unterstützt.");
+        }
+        self.check_unrotated_support_springs();
+        self.check_moment_releases();
+        let dofs = self.get_points().len() * 3;
+
+        // Kräfte mit ihren Spalten im Gleichgewicht, Nachgiebigkeiten und Lagerverschiebungen
+        let mut unknowns = Vec::new();
+        let mut columns: Vec<VectorD> = Vec::new();
+        let mut settlements = Vec::new();
+        for i in 0..self.get_springs().len() {
+            let [from, to] = self.get_spring_points()[i];
+            let t = transmatrix6x6(self.get_springs()[i].get_alpha());
+            for d in 0..3 {
+                if self.get_springs()[i].get_stiffness()[d] == 0.0 {
+                    continue;
+                }
+                let mut f = Vector6::zeros();
+                f[d] = -1.0;
+                f[d + 3] = 1.0;
+                let f = t * f;
+                let mut col = VectorD::zeros(dofs);
+                for j in 0..3 {
+                    col[from * 3 + j] += f[j];
+                    col[to * 3 + j] += f[j + 3];
+                }
+                unknowns.push(ForceUnknown::Spring { spring: i, dof: d });
+                columns.push(col);
+                settlements.push(0.0);
+            }
+        }
+        for i in 0..self.get_beams().len() {
+            let b = &self.get_beams()[i];
+            if !b.is_active() {
+                continue;
+            }
+            let g = self.beam_offset_matrix(i).transpose() * transmatrix6x6(self.get_beam_alpha(i));
+            let from = self.get_beam_from_point(i);
+            let to = self.get_beam_to_point(i);
+            for k in 0..3 {
+                if k > 0 && b.get_dofs()[3 * k - 1] {
+                    continue;
+                }
+                let f = g * System::local_force_vector(self.get_beam_lenght(i), k);
+                let mut col = VectorD::zeros(dofs);
+                for j in 0..3 {
+                    col[from * 3 + j] += f[j];
+                    col[to * 3 + j] += f[j + 3];
+                }
+                unknowns.push(ForceUnknown::Beam { beam: i, force: k });
+                columns.push(col);
+                settlements.push(0.0);
+            }
+        }
+        for i in 0..self.get_supports().len() {
+            let sup = &self.get_supports()[i];
+            let p = self.get_support_points()[i];
+            let settlement = loading.get_total_support_displacement(i);
+            for d in 0..3 {
+                if !sup.is_fixed(d) && !(sup.has_spring(d) && sup.get_feder()[d] != 0.0) {
+                    continue;
+                }
+                let mut col = VectorD::zeros(dofs);
+                col[p * 3 + d] = -1.0;
+                unknowns.push(ForceUnknown::Support { support: i, dof: d });
+                columns.push(col);
+                settlements.push(sup.get_contact_displacement(d) + settlement[d]);
+            }
+        }
+        let n = unknowns.len();
+        let flexibility = self.unknown_flexibility(&unknowns);
+        let settlements = VectorD::from_vec(settlements);
+
+        // Lasten ohne die Federkräfte der Lagerverschiebungen
+        let mut support_loads = VectorD::zeros(dofs);
+        self.support_loads(loading, &mut support_loads);
+        let p = self.load_vector_first_order(loading) - support_loads;
+
+        // Gauss-Jordan-Elimination von [A | p | I], die Auflagerkräfte zuletzt als Pivotspalten
+        let mut a = MatrixDxD::zeros(dofs, n + 1 + dofs);
+        for j in 0..n {
+            a.column_mut(j).copy_from(&columns[j]);
+        }
+        a.column_mut(n).copy_from(&p);
+        for k in 0..dofs {
+            a[(k, n + 1 + k)] = 1.0;
+        }
+        let tol = 1e-10 * a.columns(0, n).amax();
+        let mut pivots = Vec::new();
+        let mut row = 0;
+        for j in 0..n {
+            if row == dofs {
+                break;
+            }
+            let (r, max) = (row..dofs)
+                .map(|r| (r, a[(r, j)].abs()))
+                .fold((row, 0.0), |m, v| if v.1 > m.1 { v } else { m });
+            if max <= tol {
+                continue;
+            }
+            a.swap_rows(row, r);
+            let pv = a[(row, j)];
+            for c in 0..a.ncols() {
+                a[(row, c)] /= pv;
+            }
+            for r in 0..dofs {
+                if r != row && a[(r, j)] != 0.0 {
+                    let f = a[(r, j)];
+                    for c in 0..a.ncols() {
+                        a[(r, c)] -= f * a[(row, c)];
+                    }
+                }
+            }
+            pivots.push(j);
+            row += 1;
+        }
+        if row < dofs {
+            panic!("Das System ist kinematisch verschieblich.");
+        }
+        let redundants: Vec<usize> = (0..n).filter(|j| !pivots.contains(j)).collect();
+
+        // Zustände des statisch bestimmten Grundsystems
+        let state = |column: Option<usize>, redundant: Option<usize>| {
+            let mut x = VectorD::zeros(n);
+            if let Some(j) = redundant {
+                x[j] = 1.0;
+            }
+            for (r, &pc) in pivots.iter().enumerate() {
+                x[pc] = match column {
+                    Some(c) => a[(r, c)],
+                    None => 0.0,
+                } - match redundant {
+                    Some(j) => a[(r, j)],
+                    None => 0.0,
+                };
+            }
+            x
+        };
+        let x0 = state(Some(n), None);
+        let units: Vec<VectorD> = redundants.iter().map(|&j| state(None, Some(j))).collect();
+
+        // delta_ik = x_i^T F x_k, delta_i0 = x_i^T F x_0 - c^T x_i
+        let m = redundants.len();
+        let mut delta = MatrixDxD::zeros(m, m);
+        let mut delta0 = VectorD::zeros(m);
+        for i in 0..m {
+            let fx = &flexibility * &units[i];
+            for k in 0..m {
+                delta[(i, k)] = fx.dot(&units[k]);
+            }
+            delta0[i] = fx.dot(&x0) - settlements.dot(&units[i]);
+        }
+        let values = if m == 0 {
+            VectorD::zeros(0)
+        } else {
+            match delta.clone().cholesky() {
+                Some(t) => t.solve(&(-&delta0)),
+                None => panic!("Matrix nicht positiv definit."),
+            }
+        };
+        let mut x = x0.clone();
+        for i in 0..m {
+            x += values[i] * &units[i];
+        }
+
+        // Reduktionssatz: u_k = x_k^T F x - c^T x_k mit dem Zustand der Einheitslast
+        let fx = &flexibility * &x;
+        let mut u = VectorD::zeros(dofs);
+        for k in 0..dofs {
+            let xk = state(Some(n + 1 + k), None);
+            u[k] = xk.dot(&fx) - settlements.dot(&xk);
+        }
+
+        let empty = SystemLoading::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        let zeros = VectorD::zeros(dofs);
+        let basic_state = self.force_results(&unknowns, &x0, loading, &zeros);
+        let unit_states = units
+            .iter()
+            .map(|xi| self.force_results(&unknowns, xi, &empty, &zeros))
+            .collect();
+        let results = self.force_results(&unknowns, &x, loading, &u);
+
+        ForceMethodResultSet {
+            unknowns,
+            redundants,
+            flexibility: delta,
+            load_terms: delta0,
+            values,
+            basic_state,
+            unit_states,
+            results,
+        }
+    }
+
+    /// Die Nachgiebigkeitsmatrix der Kräfte aus Stäben, Federn und Lagerfedern.
+    fn unknown_flexibility(&self, unknowns: &[ForceUnknown]) -> MatrixDxD {
+        let n = unknowns.len();
+        let mut f = MatrixDxD::zeros(n, n);
+        for i in 0..n {
+            for j in 0..n {
+                f[(i, j)] = match (unknowns[i], unknowns[j]) {
+                    (
+                        ForceUnknown::Beam { beam, force },
+                        ForceUnknown::Beam {
+                            beam: beam_j,
+                            force: force_j,
+                        },
+                    ) if beam == beam_j => {
+                        let b = &self.get_beams()[beam];
+                        let l = self.get_beam_lenght(beam);
+                        let ei = b.get_emodul() * b.get_ftm();
+                        match (force, force_j) {
+                            (0, 0) => l / (b.get_emodul() * b.get_area()),
+                            (0, _) | (_, 0) => 0.0,
+                            (a, c) if a == c => l / (3.0 * ei),
+                            _ => -l / (6.0 * ei),
+                        }
+                    }
+                    (ForceUnknown::Spring { spring, dof }, _) if i == j => {
+                        1.0 / self.get_springs()[spring].get_stiffness()[dof]
+                    }
+                    (ForceUnknown::Support { support, dof }, _) if i == j => {
+                        let sup = &self.get_supports()[support];
+                        if sup.is_fixed(dof) {
+                            0.0
+                        } else {
+                            1.0 / sup.get_feder()[dof]
+                        }
+                    }
+                    _ => 0.0,
+                };
+            }
+        }
+        return f;
+    }
+
+    /// Die Stabergebnisse aus den Kräften x und den Verschiebungen u.
+    fn force_results(
+        &self,
+        unknowns: &[ForceUnknown],
+        x: &VectorD,
+        loading: &SystemLoading,
+        u: &VectorD,
+    ) -> BeamResultSet {
+        let fixed = self.beam_results_first_order(loading, &VectorD::zeros(u.len()));
+        let mut forces = vec![Vector6::zeros(); self.get_beams().len()];
+        for (k, unknown) in unknowns.iter().enumerate() {
+            if let ForceUnknown::Beam { beam, force } = *unknown {
+                forces[beam] +=
+                    x[k] * System::local_force_vector(self.get_beam_lenght(beam), force);
+            }
+        }
+        let mut r = Vec::new();
+        for i in 0..self.get_beams().len() {
+            let length = self.get_beam_lenght(i);
+            let v = self.beam_local_displacements(i, u);
+            let b = &self.get_beams()[i];
+            if !b.is_active() {
+                r.push(BeamResult::new_inactive(
+                    &v,
+                    length,
+                    b,
+                    loading.get_total_lineload_for_beam(i),
+                ));
+                continue;
+            }
+            let f = forces[i];
+            let fix = fixed.get_results()[i].get_rsks();
+            // TM Definitionen wie bei der Steifigkeitsmethode
+            let rsk = [
+                fix[0] - f[0],
+                fix[1] - f[1],
+                fix[2] + f[2],
+                fix[3] + f[3],
+                fix[4] + f[4],
+                fix[5] - f[5],
+            ];
+            r.push(BeamResult::new(
+                &rsk,
+                v.as_slice(),
+                length,
+                b,
+                fixed.get_results()[i].get_loading(),
+            ));
+        }
+        return BeamResultSet::new(r).with_displacements(u);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::stiffness::direct_stiffness::BeamResultSet;
+use crate::stiffness::modal::MassMatrix;
+use crate::stiffness::system::*;
+use crate::stiffness::time_history::RayleighDamping;
+use libm::atan2;
+use nalgebra::DVector;
+use nalgebra::Dynamic;
+use nalgebra::OMatrix;
+use std::f64::consts;
+
+type MatrixDxD = OMatrix<f64, Dynamic, Dynamic>;
+type VectorD = DVector<f64>;
+
+/// The steady state response `u(t) = Re((u_re + i u_im) e^(i Omega t))` at one frequency.
+pub struct HarmonicResult {
+    angular_frequency: f64,
+    real: BeamResultSet,
+    imaginary: BeamResultSet,
+}
+
+impl HarmonicResult {
+    pub fn get_angular_frequency(&self) -> f64 {
+        self.angular_frequency
+    }
+    pub fn get_frequency(&self) -> f64 {
+        self.angular_frequency / (2.0 * consts::PI)
+    }
+    /// The part in phase with the loading.
+    pub fn get_real(&self) -> &BeamResultSet {
+        &self.real
+    }
+    /// The part a quarter period ahead of the loading, the response is
+    /// `u(t) = u_re * cos(Omega t) - u_im * sin(Omega t)`.
+    pub fn get_imaginary(&self) -> &BeamResultSet {
+        &self.imaginary
+    }
+    pub fn get_displacement_amplitude(&self, point: usize, dof: usize) -> f64 {
+        let re = self.real.get_node_displacements()[point][dof];
+        let im = self.imaginary.get_node_displacements()[point][dof];
+        (re * re + im * im).sqrt()
+    }
+    /// The phase angle in rad, negative values lag behind the loading.
+    pub fn get_displacement_phase(&self, point: usize, dof: usize) -> f64 {
+        let re = self.real.get_node_displacements()[point][dof];
+        let im = self.imaginary.get_node_displacements()[point][dof];
+        atan2(im, re)
+    }
+    /// The amplitude of the value [N, Q, M, u, w, phi] with the index `component` at the
+    /// position of the beam.
+    pub fn get_internal_amplitude(&self, beamindex: usize, x: f64, component: usize) -> f64 {
+        let re = self.real.get_results()[beamindex].get_internals_at(x)[component];
+        let im = self.imaginary.get_results()[beamindex].get_internals_at(x)[component];
+        (re * re + im * im).sqrt()
+    }
+    pub fn get_internal_phase(&self, beamindex: usize, x: f64, component: usize) -> f64 {
+        let re = self.real.get_results()[beamindex].get_internals_at(x)[component];
+        let im = self.imaginary.get_results()[beamindex].get_internals_at(x)[component];
+        atan2(im, re)
+    }
+}
+
+pub struct HarmonicResultSet {
+    results: Vec<HarmonicResult>,
+}
+
+impl HarmonicResultSet {
+    pub fn get_results(&self) -> &[HarmonicResult] {
+        &self.results
+    }
+    /// `[f, amplitude, phase]` of the displacement of the point for every frequency.
+    pub fn get_point_response(&self, point: usize, dof: usize) -> Vec<[f64; 3]> {
+        self.results
+            .iter()
+            .map(|r| {
+                [
+                    r.get_frequency(),
+                    r.get_displacement_amplitude(point, dof),
+                    r.get_displacement_phase(point, dof),
+                ]
+            })
+            .collect()
+    }
+    /// `[f, amplitude, phase]` of the internal force of the beam for every frequency.
+    pub fn get_beam_response(&self, beamindex: usize, x: f64, component: usize) -> Vec<[f64; 3]> {
+        self.results
+            .iter()
+            .map(|r| {
+                [
+                    r.get_frequency(),
+                    r.get_internal_amplitude(beamindex, x, component),
+                    r.get_internal_phase(beamindex, x, component),
+                ]
+            })
+            .collect()
+    }
+}
+
+impl System {
+    /// The steady state response to the loading `f cos(Omega t)` for every excitation frequency
+    /// `f = Omega / (2 pi)` using first order theory.
+    ///
+    /// `(K - Omega^2 M + i Omega C) u = f` is solved as real system of twice the size. The
+    /// internal forces are recovered from the displacements without the inertia of the beams.
+    pub fn harmonic_response(
+        &self,
+        loading: &SystemLoading,
+        mass: MassMatrix,
+        damping: RayleighDamping,
+        frequencies: &[f64],
+    ) -> HarmonicResultSet {
+        let empty = SystemLoading::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        let (k, m, t) = self.reduced_dynamic_system(mass);
+        let c = damping.get_mass_factor() * &m + damping.get_stiffness_factor() * &k;
+        let f = t.transpose()
+            * (self.load_vector_first_order(loading) - self.load_vector_first_order(&empty));
+        let n = k.nrows();
+
+        let mut results = Vec::new();
+        for &freq in frequencies {
+            let omega = 2.0 * consts::PI * freq;
+            let a = &k - omega * omega * &m;
+            let oc = omega * &c;
+            // [A, -Omega C; Omega C, A] [u_re; u_im] = [f; 0]
+            let mut big = MatrixDxD::zeros(2 * n, 2 * n);
+            big.slice_mut((0, 0), (n, n)).copy_from(&a);
+            big.slice_mut((n, n), (n, n)).copy_from(&a);
+            big.slice_mut((0, n), (n, n)).copy_from(&(-&oc));
+            big.slice_mut((n, 0), (n, n)).copy_from(&oc);
+            let mut rhs = VectorD::zeros(2 * n);
+            rhs.rows_mut(0, n).copy_from(&f);
+            let u = match big.lu().solve(&rhs) {
+                Some(t) => t,
+                None => panic!("Resonanz ohne Dämpfung bei f = {}.", freq),
+            };
+            let re = &t * u.rows(0, n);
+            let im = &t * u.rows(n, n);
+            results.push(HarmonicResult {
+                angular_frequency: omega,
+                real: self.beam_results_first_order(loading, &re),
+                imaginary: self.beam_results_first_order(&empty, &im),
+            });
+        }
+        HarmonicResultSet { results }
+    }
+}
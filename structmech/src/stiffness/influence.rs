@@ -0,0 +1,353 @@
+use crate::stiffness::system::*;
+
+/// The quantity, whose influence line is computed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum InfluenceQuantity {
+    /// The value [N, Q, M, u, w, phi] with the index `component` of `BeamResult::get_internals_at`
+    /// at the position of the beam measured from the start face. A jump at the section belongs to
+    /// the right side, i.e. a load on the section acts left of it.
+    Internal {
+        beam: usize,
+        position: f64,
+        component: usize,
+    },
+    /// The reaction [x1, x2, phi3] of a support, see `System::support_reactions`.
+    Reaction { support: usize, dof: usize },
+    /// The displacement [x1, x2, phi3] of a point.
+    Displacement { point: usize, dof: usize },
+}
+
+/// An influence line sampled along a load path.
+pub struct InfluenceLine {
+    quantity: InfluenceQuantity,
+    beams: Vec<usize>,
+    positions: Vec<f64>,
+    stations: Vec<f64>,
+    values: Vec<f64>,
+}
+
+impl InfluenceLine {
+    pub fn get_quantity(&self) -> InfluenceQuantity {
+        self.quantity
+    }
+    /// The beam of every sample.
+    pub fn get_beams(&self) -> &[usize] {
+        &self.beams
+    }
+    /// The position of every sample on its beam, measured from the start face.
+    pub fn get_positions(&self) -> &[f64] {
+        &self.positions
+    }
+    /// The position of every sample along the load path.
+    pub fn get_stations(&self) -> &[f64] {
+        &self.stations
+    }
+    pub fn get_values(&self) -> &[f64] {
+        &self.values
+    }
+    /// The value at a position along the load path, linear between the samples and zero outside
+    /// of the path.
+    pub fn get_value_at(&self, station: f64) -> f64 {
+        let n = self.stations.len();
+        if n == 0 || station < self.stations[0] || station > self.stations[n - 1] {
+            return 0.0;
+        }
+        for i in 0..n - 1 {
+            let (s0, s1) = (self.stations[i], self.stations[i + 1]);
+            if station <= s1 {
+                if s1 - s0 <= 0.0 {
+                    return self.values[i + 1];
+                }
+                let t = (station - s0) / (s1 - s0);
+                return self.values[i] * (1.0 - t) + self.values[i + 1] * t;
+            }
+        }
+        return self.values[n - 1];
+    }
+    /// The area below the influence line between two positions along the load path, the parts
+    /// outside of the path are zero.
+    pub fn get_integral(&self, from: f64, to: f64) -> f64 {
+        let n = self.stations.len();
+        let mut res = 0.0;
+        for i in 0..n.saturating_sub(1) {
+            let a = self.stations[i].max(from);
+            let b = self.stations[i + 1].min(to);
+            if b > a {
+                res += 0.5 * (b - a) * (self.get_value_at(a) + self.get_value_at(b));
+            }
+        }
+        return res;
+    }
+    /// The length of the load path.
+    pub fn get_path_length(&self) -> f64 {
+        *self.stations.last().unwrap_or(&0.0)
+    }
+
+    /// One line per sample with station, beam, position and value.
+    pub fn to_csv(&self) -> String {
+        let mut s = String::from("station,beam,x,value\n");
+        for i in 0..self.values.len() {
+            s.push_str(&format!(
+                "{},{},{},{}\n",
+                self.stations[i], self.beams[i], self.positions[i], self.values[i]
+            ));
+        }
+        return s;
+    }
+}
+
+impl System {
+    /// The influence line of the quantity for the moving `load` (usually `StaticLoad::new(0.0,
+    /// 1.0, 0.0)`, a downward unit load) along the beams of the path, see `influence_lines`.
+    pub fn influence_line(
+        &self,
+        quantity: InfluenceQuantity,
+        path: &[usize],
+        samples: usize,
+        load: &StaticLoad,
+    ) -> InfluenceLine {
+        self.influence_lines(&[quantity], path, samples, load)
+            .remove(0)
+    }
+
+    /// The influence lines of the quantities for a moving load using first order theory.
+    ///
+    /// Every beam of the path is sampled at `samples + 1` equidistant positions and at the
+    /// sections of the internal quantities on it. The beams are split at these positions, the
+    /// load is moved over the new points and the system is solved with one factorisation. At a
+    /// section in the span there are two samples at the same station, the values for the load
+    /// just left and just right of it, so jumps and kinks are kept. Consecutive beams of the path
+    /// should share a point, the path position is the accumulated length of the beams.
+    pub fn influence_lines(
+        &self,
+        quantities: &[InfluenceQuantity],
+        path: &[usize],
+        samples: usize,
+        load: &StaticLoad,
+    ) -> Vec<InfluenceLine> {
+        let mut splits = vec![Vec::new(); self.get_beams().len()];
+        for &b in path {
+            let l = self.get_beam_lenght(b);
+            for s in 1..samples {
+                splits[b].push(l * s as f64 / samples as f64);
+            }
+        }
+        for q in quantities {
+            if let InfluenceQuantity::Internal { beam, position, .. } = *q {
+                splits[beam].push(position);
+            }
+        }
+        let (sys, parts) = self.subdivide(&splits);
+        let prepared = sys.prepare_first_order();
+
+        // Die Laststellungen
+        let mut beams = Vec::new();
+        let mut positions = Vec::new();
+        let mut stations = Vec::new();
+        let mut station = 0.0;
+        let mut last_point = None;
+        let mut values = vec![Vec::new(); quantities.len()];
+        for &b in path {
+            let l = self.get_beam_lenght(b);
+            let tol = 1e-9 * l;
+            let mut xs: Vec<f64> = (0..=samples)
+                .map(|s| l * s as f64 / samples as f64)
+                .collect();
+            // Die Last steht auch auf den Schnitten
+            let mut sections = Vec::new();
+            for q in quantities {
+                if let InfluenceQuantity::Internal { beam, position, .. } = *q {
+                    if beam == b && position > tol && position < l - tol {
+                        sections.push(position);
+                        if xs.iter().all(|x| (x - position).abs() > tol) {
+                            xs.push(position);
+                        }
+                    }
+                }
+            }
+            xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            for (s, &x) in xs.iter().enumerate() {
+                let point = sys.point_at(&parts[b], x, l);
+                if s == 0 && last_point == Some(point) {
+                    continue;
+                }
+                let loading = SystemLoading::new(
+                    vec![point],
+                    vec![self.face_load(b, x, load)],
+                    Vec::new(),
+                    Vec::new(),
+                );
+                let res = prepared.solve(&loading);
+                let reactions = sys.support_reactions(&loading, &res);
+                for (q, v) in quantities.iter().zip(values.iter_mut()) {
+                    v.push(match *q {
+                        InfluenceQuantity::Internal {
+                            beam,
+                            position,
+                            component,
+                        } => {
+                            let (part, x0) =
+                                section_part(&parts[beam], position, self.get_beam_lenght(beam));
+                            res.get_results()[part].get_internals_at(position - x0)[component]
+                        }
+                        InfluenceQuantity::Reaction { support, dof } => reactions[support][dof],
+                        InfluenceQuantity::Displacement { point, dof } => {
+                            res.get_node_displacements()[point][dof]
+                        }
+                    });
+                }
+                beams.push(b);
+                positions.push(x);
+                stations.push(station + x);
+
+                // Am Schnitt ein zweiter Wert für die Last rechts davon, das ist der Wert am
+                // linken Ufer des Schnittes
+                if sections.iter().any(|p| (p - x).abs() <= tol) {
+                    for (q, v) in quantities.iter().zip(values.iter_mut()) {
+                        let last = v[v.len() - 1];
+                        v.push(match *q {
+                            InfluenceQuantity::Internal {
+                                beam,
+                                position,
+                                component,
+                            } if beam == b && (position - x).abs() <= tol => {
+                                let (part, x0) = left_part(&parts[beam], position, l);
+                                res.get_results()[part].get_internals_at(position - x0)[component]
+                            }
+                            _ => last,
+                        });
+                    }
+                    beams.push(b);
+                    positions.push(x);
+                    stations.push(station + x);
+                }
+            }
+            station += l;
+            last_point = Some(self.get_beam_to_point(b));
+        }
+
+        quantities
+            .iter()
+            .zip(values.into_iter())
+            .map(|(q, v)| InfluenceLine {
+                quantity: *q,
+                beams: beams.clone(),
+                positions: positions.clone(),
+                stations: stations.clone(),
+                values: v,
+            })
+            .collect()
+    }
+
+    /// The point of the subdivided system at the position of the old beam.
+    pub(crate) fn point_at(&self, parts: &[(usize, f64)], x: f64, length: f64) -> usize {
+        let tol = 1e-9 * length;
+        for &(part, x0) in parts {
+            if (x0 - x).abs() < tol {
+                return self.get_beam_from_point(part);
+            }
+        }
+        return self.get_beam_to_point(parts[parts.len() - 1].0);
+    }
+
+    /// The load at the face of a beam end moved to its node, the points in the span have no
+    /// offsets.
+    pub(crate) fn face_load(&self, beamindex: usize, x: f64, load: &StaticLoad) -> StaticLoad {
+        let l = self.get_beam_lenght(beamindex);
+        let o = self.get_beam_global_offsets(beamindex);
+        let [fx, fy, m] = load.get_loading();
+        // Verschiebungen nach unten positiv, Verdrehung im Uhrzeigersinn
+        let e = if x < 1e-9 * l {
+            o[0]
+        } else if x > l * (1.0 - 1e-9) {
+            o[1]
+        } else {
+            [0.0, 0.0]
+        };
+        StaticLoad::new(fx, fy, m + fx * e[1] + fy * e[0])
+    }
+}
+
+/// The part left of the section in the span and its start position.
+fn left_part(parts: &[(usize, f64)], position: f64, length: f64) -> (usize, f64) {
+    let tol = 1e-9 * length;
+    let mut res = parts[0];
+    for &p in parts {
+        if p.1 < position - tol {
+            res = p;
+        }
+    }
+    return res;
+}
+
+/// The part right of the section and its start position, the part left of it at the end face.
+fn section_part(parts: &[(usize, f64)], position: f64, length: f64) -> (usize, f64) {
+    let tol = 1e-9 * length;
+    if position > length - tol {
+        return parts[parts.len() - 1];
+    }
+    let mut res = parts[0];
+    for &p in parts {
+        if p.1 <= position + tol {
+            res = p;
+        }
+    }
+    return res;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Ein Einfeldträger mit L = 10 und dem Schnitt zwischen den Abtastpunkten.
+    fn simply_supported() -> System {
+        let cs = Crosssection::new(2.1e8, 1e-2, 1e-4);
+        let beam = Beam::new(cs, [false; 6], [0.0; 6], 0.0, 0.0);
+        System::new(
+            vec![Point::new(0.0, 0.0), Point::new(10.0, 0.0)],
+            vec![[0, 1]],
+            vec![beam],
+            vec![0, 1],
+            vec![
+                Support::new(0.0, [false, false, true], [0.0; 3]),
+                Support::new(0.0, [true, false, true], [0.0; 3]),
+            ],
+        )
+    }
+
+    fn section(component: usize) -> InfluenceQuantity {
+        InfluenceQuantity::Internal {
+            beam: 0,
+            position: 3.3,
+            component,
+        }
+    }
+
+    #[test]
+    fn moment_line_has_its_kink_at_the_section() {
+        let sys = simply_supported();
+        let line = sys.influence_line(section(2), &[0], 10, &StaticLoad::new(0.0, 1.0, 0.0));
+        let (a, b, l) = (3.3, 6.7, 10.0);
+        assert!((line.get_value_at(a) - a * b / l).abs() < 1e-6);
+        for x in [1.0, 3.0, 4.0, 8.0] {
+            let exact = if x <= a { x * b / l } else { a * (l - x) / l };
+            assert!((line.get_value_at(x) - exact).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn shear_line_jumps_at_the_section() {
+        let sys = simply_supported();
+        let line = sys.influence_line(section(1), &[0], 10, &StaticLoad::new(0.0, 1.0, 0.0));
+        let (a, l) = (3.3, 10.0);
+        let at: Vec<usize> = (0..line.get_stations().len())
+            .filter(|&i| (line.get_stations()[i] - a).abs() < 1e-9)
+            .collect();
+        assert_eq!(at.len(), 2);
+        // Last links vom Schnitt: Q = -B, rechts davon: Q = A
+        assert!((line.get_values()[at[0]] + a / l).abs() < 1e-6);
+        assert!((line.get_values()[at[1]] - (l - a) / l).abs() < 1e-6);
+        assert!((line.get_value_at(a - 0.1) + (a - 0.1) / l).abs() < 1e-6);
+        assert!((line.get_value_at(a + 0.1) - (l - a - 0.1) / l).abs() < 1e-6);
+    }
+}
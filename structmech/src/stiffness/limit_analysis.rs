@@ -0,0 +1,516 @@
+use crate::stiffness::direct_stiffness::transmatrix6x6;
+use crate::stiffness::system::*;
+use nalgebra::DVector;
+use nalgebra::Dynamic;
+use nalgebra::OMatrix;
+use nalgebra::SVector;
+
+type MatrixDxD = OMatrix<f64, Dynamic, Dynamic>;
+type VectorD = DVector<f64>;
+type Vector6 = SVector<f64, 6>;
+
+/// A checked section of the limit analysis.
+pub struct LimitSection {
+    beam: usize,
+    position: f64,
+    moment: f64,
+    normal_force: f64,
+    rotation: f64,
+}
+
+impl LimitSection {
+    pub fn get_beam(&self) -> usize {
+        self.beam
+    }
+    /// The position on the beam measured from the start face.
+    pub fn get_position(&self) -> f64 {
+        self.position
+    }
+    /// The bending moment of the statically admissible state at collapse.
+    pub fn get_moment(&self) -> f64 {
+        self.moment
+    }
+    pub fn get_normal_force(&self) -> f64 {
+        self.normal_force
+    }
+    /// The rotation of the plastic hinge in the mechanism, zero if there is no hinge.
+    pub fn get_rotation(&self) -> f64 {
+        self.rotation
+    }
+}
+
+pub struct LimitAnalysisResultSet {
+    load_factor: f64,
+    upper_bound: f64,
+    sections: Vec<LimitSection>,
+    mechanism: Vec<[f64; 3]>,
+}
+
+impl LimitAnalysisResultSet {
+    /// The largest load factor of a statically admissible state (static theorem), infinite if
+    /// no mechanism exists.
+    pub fn get_load_factor(&self) -> f64 {
+        self.load_factor
+    }
+    /// The load factor of the mechanism after the kinematic theorem. It equals the load factor
+    /// up to rounding errors.
+    pub fn get_upper_bound(&self) -> f64 {
+        self.upper_bound
+    }
+    pub fn is_mechanism(&self) -> bool {
+        self.load_factor.is_finite()
+    }
+    /// The beam ends and the sections in the spans with the forces at collapse.
+    pub fn get_sections(&self) -> &[LimitSection] {
+        &self.sections
+    }
+    /// The sections with a rotating plastic hinge in the mechanism.
+    pub fn get_hinges(&self) -> Vec<&LimitSection> {
+        let max = self
+            .sections
+            .iter()
+            .map(|s| s.rotation.abs())
+            .fold(0.0, f64::max);
+        self.sections
+            .iter()
+            .filter(|s| s.rotation.abs() > 1e-6 * max)
+            .collect()
+    }
+    /// The velocities x_1, x_2, phi_3 of the points in the mechanism, scaled so that the variable
+    /// loading does unit work.
+    pub fn get_mechanism(&self) -> &[[f64; 3]] {
+        &self.mechanism
+    }
+}
+
+/// Ein Kontrollschnitt am Ende eines Teilstabes.
+struct Section {
+    beam: usize,
+    position: f64,
+    part: usize,
+    end: usize,
+}
+
+/// Das Ergebnis des Simplex-Verfahrens.
+enum LinearProgram {
+    /// Primal- und Dualvariablen der Zeilen.
+    Optimal(VectorD, VectorD),
+    Unbounded,
+    Infeasible,
+}
+
+impl System {
+    /// The rigid-plastic limit analysis with first order theory by linear programming.
+    ///
+    /// The `constant` loading is applied with the factor 1, the factor of the `variable` loading
+    /// is maximised under the equilibrium of the points and `|M| <= M_pl`, or
+    /// `|M| / M_pl + |N| / N_pl <= 1` with the plastic normal force, at the beam ends and at
+    /// `samples - 1` points of every beam (static theorem). The mechanism is taken from the dual
+    /// solution. Springs are treated as rigid, only moment releases are allowed and support
+    /// displacements are not considered.
+    pub fn limit_analysis(
+        &self,
+        constant: &SystemLoading,
+        variable: &SystemLoading,
+        samples: usize,
+    ) -> LimitAnalysisResultSet {
+        self.check_moment_releases();
+        let empty = SystemLoading::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        let lengths: Vec<f64> = (0..self.get_beams().len())
+            .map(|b| self.get_beam_lenght(b))
+            .collect();
+        let positions: Vec<Vec<f64>> = lengths
+            .iter()
+            .map(|&l| {
+                (1..samples)
+                    .map(|s| l * s as f64 / samples as f64)
+                    .collect()
+            })
+            .collect();
+        let (sys, parts) = self.subdivide(&positions);
+        let constant = constant.subdivide(&lengths, &parts);
+        let variable = variable.subdivide(&lengths, &parts);
+
+        let mut sections = Vec::new();
+        for b in 0..parts.len() {
+            let p = &parts[b];
+            if !sys.get_beams()[p[0].0].is_active() {
+                continue;
+            }
+            sections.push(Section {
+                beam: b,
+                position: 0.0,
+                part: p[0].0,
+                end: 0,
+            });
+            for k in 0..p.len() {
+                sections.push(Section {
+                    beam: b,
+                    position: if k + 1 < p.len() {
+                        p[k + 1].1
+                    } else {
+                        lengths[b]
+                    },
+                    part: p[k].0,
+                    end: 1,
+                });
+            }
+        }
+
+        // Variablen: lambda, die Stabkräfte [N, M_a, M_b] und die Federkräfte
+        let dofs = sys.get_points().len() * 3;
+        let mut forces = vec![[None; 3]; sys.get_beams().len()];
+        let mut count = 1;
+        for i in 0..sys.get_beams().len() {
+            let b = &sys.get_beams()[i];
+            if !b.is_active() {
+                continue;
+            }
+            for k in 0..3 {
+                if k == 0 || !b.get_dofs()[3 * k - 1] {
+                    forces[i][k] = Some(count);
+                    count += 1;
+                }
+            }
+        }
+        let mut spring = MatrixDxD::zeros(dofs, dofs);
+        let mut unused = VectorD::zeros(dofs);
+        sys.springs(&mut spring, &mut unused);
+        sys.supports(&empty, &mut spring, &mut unused);
+        let spring_columns: Vec<usize> = (0..dofs)
+            .filter(|&j| spring.column(j).amax() > 0.0)
+            .collect();
+        let free = count + spring_columns.len();
+
+        // Gleichgewicht der Knoten B s + S r = p_c + lambda p_v
+        let mut equilibrium = MatrixDxD::zeros(dofs, free);
+        for i in 0..sys.get_beams().len() {
+            if !sys.get_beams()[i].is_active() {
+                continue;
+            }
+            let g = sys.beam_offset_matrix(i).transpose() * transmatrix6x6(sys.get_beam_alpha(i));
+            let from = sys.get_beam_from_point(i);
+            let to = sys.get_beam_to_point(i);
+            for k in 0..3 {
+                if let Some(v) = forces[i][k] {
+                    let col = g * System::local_force_vector(sys.get_beam_lenght(i), k);
+                    for j in 0..3 {
+                        equilibrium[(from * 3 + j, v)] += col[j];
+                        equilibrium[(to * 3 + j, v)] += col[j + 3];
+                    }
+                }
+            }
+        }
+        for (k, &j) in spring_columns.iter().enumerate() {
+            equilibrium
+                .column_mut(count + k)
+                .copy_from(&spring.column(j));
+        }
+        let pc = sys.load_vector_first_order(&constant);
+        let pv = sys.load_vector_first_order(&variable);
+        equilibrium.column_mut(0).copy_from(&(-&pv));
+        let (t, _) = sys.dof_transformation(&empty);
+        let equilibrium = t.transpose() * equilibrium;
+        let rhs = t.transpose() * &pc;
+        let neq = equilibrium.nrows();
+
+        // Momente der Volleinspannung aus den Streckenlasten der Teilstäbe
+        let zeros = VectorD::zeros(dofs);
+        let fixed_c = sys.beam_results_first_order(&constant, &zeros);
+        let fixed_v = sys.beam_results_first_order(&variable, &zeros);
+        let fixed_moment =
+            |res: &crate::stiffness::direct_stiffness::BeamResultSet, part: usize, end: usize| {
+                let r = &res.get_results()[part];
+                r.get_internals_at(end as f64 * r.get_beam_lenght())[2]
+            };
+
+        // Fließbedingungen sm M / M_pl + sn N / N_pl <= 1
+        let mut rows: Vec<(usize, f64, f64, VectorD, f64)> = Vec::new();
+        for (si, s) in sections.iter().enumerate() {
+            let b = &sys.get_beams()[s.part];
+            let mpl = match b.get_plastic_moment() {
+                Some(t) => t,
+                None => continue,
+            };
+            let signs: Vec<(f64, f64)> = match b.get_plastic_normal_force() {
+                Some(_) => vec![(1.0, 1.0), (1.0, -1.0), (-1.0, 1.0), (-1.0, -1.0)],
+                None => vec![(1.0, 0.0), (-1.0, 0.0)],
+            };
+            let mc = fixed_moment(&fixed_c, s.part, s.end);
+            let mv = fixed_moment(&fixed_v, s.part, s.end);
+            for (sm, sn) in signs {
+                let mut row = VectorD::zeros(free);
+                // Inneres Moment: M_a am Anfang, -M_b am Ende
+                if let Some(v) = forces[s.part][1 + s.end] {
+                    row[v] = sm * if s.end == 0 { 1.0 } else { -1.0 } / mpl;
+                }
+                if let Some(npl) = b.get_plastic_normal_force() {
+                    row[forces[s.part][0].unwrap()] = sn / npl;
+                }
+                row[0] = sm * mv / mpl;
+                rows.push((si, sm, sn, row, 1.0 - sm * mc / mpl));
+            }
+        }
+
+        // Standardform mit x = x+ - x- für die freien Variablen und Schlupfvariablen
+        let nrows = neq + rows.len();
+        let ncols = 1 + 2 * (free - 1) + rows.len();
+        let mut a = MatrixDxD::zeros(nrows, ncols);
+        let mut b = VectorD::zeros(nrows);
+        let mut c = VectorD::zeros(ncols);
+        c[0] = 1.0;
+        let set_row = |a: &mut MatrixDxD, i: usize, row: &VectorD| {
+            a[(i, 0)] = row[0];
+            for v in 1..free {
+                a[(i, 2 * v - 1)] = row[v];
+                a[(i, 2 * v)] = -row[v];
+            }
+        };
+        for i in 0..neq {
+            set_row(&mut a, i, &equilibrium.row(i).transpose());
+            b[i] = rhs[i];
+        }
+        for (k, (_, _, _, row, r)) in rows.iter().enumerate() {
+            set_row(&mut a, neq + k, row);
+            a[(neq + k, 2 * free - 1 + k)] = 1.0;
+            b[neq + k] = *r;
+        }
+
+        let (x, y) = match simplex(&a, &b, &c) {
+            LinearProgram::Optimal(x, y) => (x, y),
+            LinearProgram::Unbounded => {
+                return LimitAnalysisResultSet {
+                    load_factor: f64::INFINITY,
+                    upper_bound: f64::INFINITY,
+                    sections: Vec::new(),
+                    mechanism: Vec::new(),
+                }
+            }
+            LinearProgram::Infeasible => {
+                panic!("Die ständige Belastung überschreitet die plastische Tragfähigkeit.")
+            }
+        };
+        let load_factor = x[0];
+        let value = |v: Option<usize>| match v {
+            Some(v) => x[2 * v - 1] - x[2 * v],
+            None => 0.0,
+        };
+
+        // Mechanismus aus den Dualvariablen des Gleichgewichts u = -T y
+        let u = -(&t * y.rows(0, neq));
+        let rotations = |part: usize| -> [f64; 3] {
+            let l = sys.get_beam_lenght(part);
+            let v = sys.beam_local_displacements(part, &u);
+            [
+                v[3] - v[0],
+                v[1] / l + v[2] - v[4] / l,
+                -(v[1] / l + v[5] - v[4] / l),
+            ]
+        };
+        let mut work_c = pc.dot(&u);
+        let mut work_v = pv.dot(&u);
+        for i in 0..sys.get_beams().len() {
+            if !sys.get_beams()[i].is_active() {
+                continue;
+            }
+            let d = rotations(i);
+            for end in 0..2 {
+                work_c += fixed_moment(&fixed_c, i, end) * d[1 + end];
+                work_v += fixed_moment(&fixed_v, i, end) * d[1 + end];
+            }
+        }
+        let mut dissipation = 0.0;
+        for (k, (si, _, _, _, _)) in rows.iter().enumerate() {
+            let s = &sections[*si];
+            let b = &sys.get_beams()[s.part];
+            if b.get_plastic_normal_force().is_some() {
+                dissipation += y[neq + k];
+            } else if rows[k].1 > 0.0 {
+                dissipation += b.get_plastic_moment().unwrap() * rotations(s.part)[1 + s.end].abs();
+            }
+        }
+
+        let result_sections = sections
+            .iter()
+            .map(|s| {
+                let m = value(forces[s.part][1 + s.end]) * if s.end == 0 { 1.0 } else { -1.0 }
+                    + fixed_moment(&fixed_c, s.part, s.end)
+                    + load_factor * fixed_moment(&fixed_v, s.part, s.end);
+                LimitSection {
+                    beam: s.beam,
+                    position: s.position,
+                    moment: m,
+                    normal_force: value(forces[s.part][0]),
+                    rotation: rotations(s.part)[1 + s.end] / work_v,
+                }
+            })
+            .collect();
+
+        LimitAnalysisResultSet {
+            load_factor,
+            upper_bound: (dissipation - work_c) / work_v,
+            sections: result_sections,
+            mechanism: (0..self.get_points().len())
+                .map(|p| {
+                    [
+                        u[p * 3] / work_v,
+                        u[p * 3 + 1] / work_v,
+                        u[p * 3 + 2] / work_v,
+                    ]
+                })
+                .collect(),
+        }
+    }
+
+    /// Die lokalen Stabendkräfte zu N, M_a oder M_b im Vorzeichen der Steifigkeitsmatrix.
+    pub(crate) fn local_force_vector(length: f64, force: usize) -> Vector6 {
+        match force {
+            0 => Vector6::new(-1.0, 0.0, 0.0, 1.0, 0.0, 0.0),
+            1 => Vector6::new(0.0, 1.0 / length, 1.0, 0.0, -1.0 / length, 0.0),
+            _ => Vector6::new(0.0, 1.0 / length, 0.0, 0.0, -1.0 / length, 1.0),
+        }
+    }
+}
+
+/// Maximiert c^T x mit A x = b und x >= 0 nach dem zweiphasigen Simplex-Verfahren.
+fn simplex(a: &MatrixDxD, b: &VectorD, c: &VectorD) -> LinearProgram {
+    let (m, n) = a.shape();
+    let width = n + m + 1;
+    let last = width - 1;
+    // Tableau [A | I | b] mit b >= 0 und normierten Zeilen, die Einheitsspalten sind künstlich
+    let mut tab = vec![vec![0.0; width]; m + 1];
+    let mut scale = vec![1.0; m];
+    for i in 0..m {
+        let norm = a.row(i).amax().max(b[i].abs());
+        scale[i] = if norm > 0.0 { 1.0 / norm } else { 1.0 };
+        if b[i] < 0.0 {
+            scale[i] = -scale[i];
+        }
+        for j in 0..n {
+            tab[i][j] = scale[i] * a[(i, j)];
+        }
+        tab[i][n + i] = 1.0;
+        tab[i][last] = scale[i] * b[i];
+    }
+    let mut basis: Vec<usize> = (n..n + m).collect();
+
+    // Phase 1: Maximieren von -Summe der künstlichen Variablen
+    for j in 0..width {
+        if j < n || j == last {
+            tab[m][j] = -(0..m).map(|i| tab[i][j]).sum::<f64>();
+        }
+    }
+    simplex_iterations(&mut tab, &mut basis, n);
+    if tab[m][last] < -1e-9 * (m as f64).max(1.0) {
+        return LinearProgram::Infeasible;
+    }
+    // Künstliche Variablen aus der Basis entfernen, in redundanten Zeilen bleiben sie
+    for i in 0..m {
+        if basis[i] >= n {
+            if let Some(j) = (0..n).find(|&j| tab[i][j].abs() > 1e-9) {
+                pivot(&mut tab, i, j);
+                basis[i] = j;
+            }
+        }
+    }
+
+    // Phase 2
+    for j in 0..width {
+        let cj = if j < n { c[j] } else { 0.0 };
+        tab[m][j] = (0..m)
+            .map(|i| {
+                if basis[i] < n {
+                    c[basis[i]] * tab[i][j]
+                } else {
+                    0.0
+                }
+            })
+            .sum::<f64>()
+            - cj;
+    }
+    if !simplex_iterations(&mut tab, &mut basis, n) {
+        return LinearProgram::Unbounded;
+    }
+
+    let mut x = VectorD::zeros(n);
+    for i in 0..m {
+        if basis[i] < n {
+            x[basis[i]] = tab[i][last];
+        }
+    }
+    // y = c_B B^-1 steht in den Spalten der künstlichen Variablen
+    let y = VectorD::from_iterator(m, (0..m).map(|i| tab[m][n + i] * scale[i]));
+    return LinearProgram::Optimal(x, y);
+}
+
+/// Austauschschritte bis zum Optimum, false bei unbeschränkter Zielfunktion. Nach vielen
+/// degenerierten Schritten wird nach der Regel von Bland gewählt.
+fn simplex_iterations(tab: &mut [Vec<f64>], basis: &mut [usize], columns: usize) -> bool {
+    let m = basis.len();
+    let last = tab[0].len() - 1;
+    let tol = 1e-10;
+    let mut degenerate = 0;
+    loop {
+        let bland = degenerate > 50;
+        let mut entering = None;
+        let mut best = -tol;
+        for j in 0..columns {
+            if tab[m][j] < best {
+                entering = Some(j);
+                if bland {
+                    break;
+                }
+                best = tab[m][j];
+            }
+        }
+        let e = match entering {
+            Some(e) => e,
+            None => return true,
+        };
+        let mut leaving: Option<usize> = None;
+        let mut ratio = f64::INFINITY;
+        for i in 0..m {
+            if tab[i][e] > tol {
+                let r = tab[i][last] / tab[i][e];
+                let better = match leaving {
+                    None => true,
+                    Some(l) => r < ratio - 1e-12 || (r <= ratio + 1e-12 && basis[i] < basis[l]),
+                };
+                if better {
+                    leaving = Some(i);
+                    ratio = r;
+                }
+            }
+        }
+        let l = match leaving {
+            Some(l) => l,
+            None => return false,
+        };
+        if ratio < tol {
+            degenerate += 1;
+        } else {
+            degenerate = 0;
+        }
+        pivot(tab, l, e);
+        basis[l] = e;
+    }
+}
+
+fn pivot(tab: &mut [Vec<f64>], row: usize, col: usize) {
+    let p = tab[row][col];
+    for v in tab[row].iter_mut() {
+        *v /= p;
+    }
+    let pivot_row = tab[row].clone();
+    for (i, r) in tab.iter_mut().enumerate() {
+        if i == row {
+            continue;
+        }
+        let f = r[col];
+        if f != 0.0 {
+            for (v, pv) in r.iter_mut().zip(pivot_row.iter()) {
+                *v -= f * pv;
+            }
+        }
+    }
+}
@@ -6,3 +6,66 @@ pub mod direct_stiffness;
 /// using a specific loading.
 pub mod first_order;
 pub mod system;
+
+/// Iterative solution of systems with nonlinear semi-rigid beam connections.
+pub mod semi_rigid;
+
+/// Iterative solution of systems with tension-only and compression-only members.
+pub mod unilateral;
+
+/// Cables with sag, using the Ernst modulus.
+pub mod cable;
+
+/// Load cases and their combinations after EN 1990.
+pub mod combination;
+
+/// Min/max envelopes of the results of many load combinations.
+pub mod envelope;
+
+/// Solution of many loadings with one factorised stiffness matrix.
+pub mod prepared;
+
+/// Influence lines of internal forces, reactions and displacements.
+pub mod influence;
+
+/// Moving load trains evaluated with influence lines.
+pub mod moving_load;
+
+/// Mass matrices and natural frequencies.
+pub mod modal;
+
+/// Linear time-history analysis with Newmark and HHT-alpha integration.
+pub mod time_history;
+
+/// Response spectrum analysis with modal combination.
+pub mod response_spectrum;
+
+/// Steady-state response to harmonic loadings.
+pub mod harmonic;
+
+/// Elastic-plastic analysis with plastic hinges.
+pub mod plastic;
+
+/// Rigid-plastic limit analysis by linear programming.
+pub mod limit_analysis;
+
+/// Nonlinear static pushover analysis with lateral load patterns.
+pub mod pushover;
+
+/// The force method with redundants and flexibility matrix.
+pub mod force_method;
+
+/// The transfer matrix method for continuous beams and chains.
+pub mod transfer_matrix;
+
+/// Displacements at any point by the principle of virtual work.
+pub mod virtual_work;
+
+/// The moment distribution method after Hardy Cross.
+pub mod moment_distribution;
+
+/// A recording of the steps of the stiffness method for teaching.
+pub mod trace;
+
+/// Randomised exercises with answer keys.
+pub mod exercise;
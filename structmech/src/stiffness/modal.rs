@@ -0,0 +1,219 @@
+use crate::stiffness::system::*;
+use nalgebra::Dynamic;
+use nalgebra::OMatrix;
+use nalgebra::SMatrix;
+use nalgebra::{DVector, SVector};
+use std::f64::consts;
+
+type Matrix6x6 = SMatrix<f64, 6, 6>;
+type Vector6 = SVector<f64, 6>;
+type MatrixDxD = OMatrix<f64, Dynamic, Dynamic>;
+type VectorD = DVector<f64>;
+
+/// The mass matrix of the beams.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum MassMatrix {
+    /// The consistent mass matrix with the shape functions of the stiffness matrix.
+    ///
+    /// The transverse shape functions of beams with released dofs (hinges, semi-rigid ends)
+    /// follow from the static condensation of the releases, e.g. linear ones for a truss bar.
+    Consistent,
+    /// Half of the mass of a beam on the translational dofs of each end.
+    Lumped,
+}
+
+impl Beam {
+    /// Die lokale Massenmatrix, die Gelenke werden durch die statische Kondensation der
+    /// Biegesteifigkeit berücksichtigt.
+    fn local_mass_matrix(&self, length: f64, kind: MassMatrix) -> Matrix6x6 {
+        let m = self.get_mass() * length;
+        let l = length;
+        let lumped =
+            Matrix6x6::from_diagonal(&Vector6::new(0.5 * m, 0.5 * m, 0.0, 0.5 * m, 0.5 * m, 0.0));
+        if kind == MassMatrix::Lumped {
+            return lumped;
+        }
+        let t = match self.release_transformation(length) {
+            Some(t) => t,
+            // Ohne Querverbindung zu den Knoten gibt es keine Ansatzfunktion
+            None => return lumped,
+        };
+        let a = m / 6.0;
+        let b = m / 420.0;
+        #[rustfmt::skip]
+        let res = Matrix6x6::new(
+            2.0 * a, 0.0,           0.0,               1.0 * a, 0.0,           0.0,
+            0.0,     156.0 * b,     22.0 * l * b,      0.0,     54.0 * b,      -13.0 * l * b,
+            0.0,     22.0 * l * b,  4.0 * l * l * b,   0.0,     13.0 * l * b,  -3.0 * l * l * b,
+            1.0 * a, 0.0,           0.0,               2.0 * a, 0.0,           0.0,
+            0.0,     54.0 * b,      13.0 * l * b,      0.0,     156.0 * b,     -22.0 * l * b,
+            0.0,     -13.0 * l * b, -3.0 * l * l * b,  0.0,     -22.0 * l * b, 4.0 * l * l * b,
+        );
+        return t.transpose() * res * t;
+    }
+
+    /// Die Stabendverschiebungen aus den Knotenverschiebungen v = T u. Die gelösten
+    /// Querfreiheitsgrade folgen aus dem Gleichgewicht der Biegesteifigkeit mit den
+    /// Gelenkfedern, die Normalkraft bleibt gekoppelt.
+    fn release_transformation(&self, length: f64) -> Option<Matrix6x6> {
+        let dofs = [1, 2, 4, 5];
+        let released: Vec<usize> = (0..4).filter(|&i| self.get_dofs()[dofs[i]]).collect();
+        let mut t = Matrix6x6::identity();
+        if released.is_empty() {
+            return Some(t);
+        }
+        let l = length;
+        let ei = self.get_emodul() * self.get_ftm() / l.powi(3);
+        #[rustfmt::skip]
+        let k = [
+            [12.0 * ei,      6.0 * l * ei,      -12.0 * ei,     6.0 * l * ei],
+            [6.0 * l * ei,   4.0 * l * l * ei,  -6.0 * l * ei,  2.0 * l * l * ei],
+            [-12.0 * ei,     -6.0 * l * ei,     12.0 * ei,      -6.0 * l * ei],
+            [6.0 * l * ei,   2.0 * l * l * ei,  -6.0 * l * ei,  4.0 * l * l * ei],
+        ];
+        let r = released.len();
+        let mut krr = MatrixDxD::zeros(r, r);
+        let mut rhs = MatrixDxD::zeros(r, 4);
+        for a in 0..r {
+            let spring = self.get_dofstiffness()[dofs[released[a]]];
+            for b in 0..r {
+                krr[(a, b)] = k[released[a]][released[b]];
+            }
+            krr[(a, a)] += spring;
+            for j in 0..4 {
+                if !released.contains(&j) {
+                    rhs[(a, j)] = -k[released[a]][j];
+                }
+            }
+            rhs[(a, released[a])] = spring;
+        }
+        let x = krr.try_inverse()? * rhs;
+        for a in 0..r {
+            for j in 0..4 {
+                t[(dofs[released[a]], dofs[j])] = x[(a, j)];
+            }
+        }
+        return Some(t);
+    }
+}
+
+/// A natural mode of vibration.
+pub struct Mode {
+    angular_frequency: f64,
+    shape: Vec<[f64; 3]>,
+}
+
+impl Mode {
+    /// The angular frequency omega in rad per time unit.
+    pub fn get_angular_frequency(&self) -> f64 {
+        self.angular_frequency
+    }
+    /// The natural frequency f = omega / (2 pi).
+    pub fn get_frequency(&self) -> f64 {
+        self.angular_frequency / (2.0 * consts::PI)
+    }
+    pub fn get_period(&self) -> f64 {
+        1.0 / self.get_frequency()
+    }
+    /// The displacements x_1, x_2, phi_3 of every point, normalised to the mass matrix.
+    pub fn get_shape(&self) -> &[[f64; 3]] {
+        &self.shape
+    }
+}
+
+pub struct ModalResultSet {
+    modes: Vec<Mode>,
+}
+
+impl ModalResultSet {
+    /// The modes with increasing frequency.
+    pub fn get_modes(&self) -> &[Mode] {
+        &self.modes
+    }
+}
+
+impl System {
+    /// Die globale Massenmatrix der Stäbe und Punktmassen.
+    pub(crate) fn mass_matrix(&self, kind: MassMatrix) -> MatrixDxD {
+        let total_dofs = self.get_points().len() * 3;
+        let mut mass = MatrixDxD::zeros(total_dofs, total_dofs);
+        let mut unused = VectorD::zeros(total_dofs);
+        for i in 0..self.get_beams().len() {
+            if !self.get_beams()[i].is_active() {
+                continue;
+            }
+            let from = self.get_beam_from_point(i);
+            let to = self.get_beam_to_point(i);
+            let length = self.get_beam_lenght(i);
+
+            let m = self.get_beams()[i].local_mass_matrix(length, kind);
+            let (f, _) = self.beam_global_stiffness(i, m, Vector6::zeros());
+            System::assemble_beam(from, to, &f, &Vector6::zeros(), &mut mass, &mut unused);
+        }
+        for i in 0..self.get_masses().len() {
+            let p = self.get_mass_points()[i];
+            for j in 0..3 {
+                mass[(p * 3 + j, p * 3 + j)] += self.get_masses()[i][j];
+            }
+        }
+        return mass;
+    }
+
+    /// Steifigkeits- und Massenmatrix der unabhängigen Freiheitsgrade und die Transformation T.
+    pub(crate) fn reduced_dynamic_system(
+        &self,
+        kind: MassMatrix,
+    ) -> (MatrixDxD, MatrixDxD, MatrixDxD) {
+        let empty = SystemLoading::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        let (t, _) = self.dof_transformation(&empty);
+        let k = t.transpose() * self.global_stiffness_first_order() * &t;
+        let m = t.transpose() * self.mass_matrix(kind) * &t;
+        return (k, m, t);
+    }
+
+    /// Solves the generalized eigenvalue problem `K phi = omega^2 M phi` using first order
+    /// theory and returns at most `count` modes with the lowest frequencies.
+    ///
+    /// With the Cholesky factorisation `K = L L^T` the symmetric problem
+    /// `L^-1 M L^-T y = 1 / omega^2 y` is solved, so a singular mass matrix (e.g. lumped masses
+    /// without rotational inertia) is allowed. Dofs without mass do not have a mode.
+    pub fn modal_analysis(&self, kind: MassMatrix, count: usize) -> ModalResultSet {
+        let (k, m, t) = self.reduced_dynamic_system(kind);
+        let l = match k.cholesky() {
+            Some(t) => t.l(),
+            None => panic!("Matrix nicht positiv definit."),
+        };
+        let lm = l.solve_lower_triangular(&m).unwrap();
+        let a = l.solve_lower_triangular(&lm.transpose()).unwrap();
+        let a = 0.5 * (&a + a.transpose());
+        let eigen = a.symmetric_eigen();
+
+        let mu_max = eigen.eigenvalues.iter().cloned().fold(0.0, f64::max);
+        let mut order: Vec<usize> = (0..eigen.eigenvalues.len())
+            .filter(|&i| eigen.eigenvalues[i] > 1e-12 * mu_max)
+            .collect();
+        // Größtes mu = kleinste Eigenfrequenz
+        order.sort_by(|&a, &b| {
+            eigen.eigenvalues[b]
+                .partial_cmp(&eigen.eigenvalues[a])
+                .unwrap()
+        });
+
+        let lt = l.transpose();
+        let mut modes = Vec::new();
+        for &i in order.iter().take(count) {
+            let mu = eigen.eigenvalues[i];
+            let y = eigen.eigenvectors.column(i).into_owned();
+            // x^T M x = y^T A y = mu
+            let x = lt.solve_upper_triangular(&y).unwrap() / mu.sqrt();
+            let u = &t * x;
+            modes.push(Mode {
+                angular_frequency: (1.0 / mu).sqrt(),
+                shape: (0..u.len() / 3)
+                    .map(|p| [u[p * 3], u[p * 3 + 1], u[p * 3 + 2]])
+                    .collect(),
+            });
+        }
+        ModalResultSet { modes }
+    }
+}
@@ -0,0 +1,304 @@
+use crate::stiffness::system::*;
+use nalgebra::Dynamic;
+use nalgebra::OMatrix;
+
+type MatrixDxD = OMatrix<f64, Dynamic, Dynamic>;
+
+/// Die Höchstzahl der Ausgleichsschritte.
+const MAX_ITERATIONS: usize = 1000;
+
+/// One iteration of the distribution table, the values are `[start, end]` of every beam.
+pub struct MomentDistributionStep {
+    balance: Vec<[f64; 2]>,
+    carry_over: Vec<[f64; 2]>,
+}
+
+impl MomentDistributionStep {
+    /// The distributed moments, which balance the joints.
+    pub fn get_balance(&self) -> &[[f64; 2]] {
+        &self.balance
+    }
+    /// The moments carried over from the other end of the beam.
+    pub fn get_carry_over(&self) -> &[[f64; 2]] {
+        &self.carry_over
+    }
+}
+
+/// The distribution table of the moment distribution method.
+///
+/// All moments are end moments acting on the beam, clockwise positive. The bending moments of
+/// the beam are `M(0) = M_start` and `M(l) = -M_end`.
+pub struct MomentDistributionResultSet {
+    distribution_factors: Vec<[f64; 2]>,
+    carry_over_factors: Vec<[f64; 2]>,
+    fixed_end_moments: Vec<[f64; 2]>,
+    steps: Vec<MomentDistributionStep>,
+    moments: Vec<[f64; 2]>,
+    rotations: Vec<f64>,
+    converged: bool,
+}
+
+impl MomentDistributionResultSet {
+    /// The share of the beam ends at the unbalanced moment of their joint, zero at fixed ends.
+    pub fn get_distribution_factors(&self) -> &[[f64; 2]] {
+        &self.distribution_factors
+    }
+    /// The factors, with which a distributed moment is carried over to the other end of the
+    /// beam, 1/2 for a prismatic beam and zero for a beam with a hinge.
+    pub fn get_carry_over_factors(&self) -> &[[f64; 2]] {
+        &self.carry_over_factors
+    }
+    pub fn get_fixed_end_moments(&self) -> &[[f64; 2]] {
+        &self.fixed_end_moments
+    }
+    pub fn get_steps(&self) -> &[MomentDistributionStep] {
+        &self.steps
+    }
+    /// The final end moments, the sum of the columns of the table.
+    pub fn get_end_moments(&self) -> &[[f64; 2]] {
+        &self.moments
+    }
+    /// The rotations of the points, zero at points, which are not balanced.
+    pub fn get_rotations(&self) -> &[f64] {
+        &self.rotations
+    }
+    /// Whether the unbalanced moments have fallen below the tolerance.
+    pub fn is_converged(&self) -> bool {
+        self.converged
+    }
+
+    /// The distribution table as text with one column per beam end.
+    pub fn to_table(&self) -> String {
+        let ends: Vec<(usize, usize)> = (0..self.moments.len())
+            .flat_map(|b| [(b, 0), (b, 1)])
+            .collect();
+        let mut s = format!("{:<8}", "beam");
+        for &(b, k) in &ends {
+            s.push_str(&format!("{:>12}", format!("{} {}", b, ["start", "end"][k])));
+        }
+        s.push('\n');
+        let mut row = |name: &str, values: &[[f64; 2]]| {
+            s.push_str(&format!("{:<8}", name));
+            for &(b, k) in &ends {
+                s.push_str(&format!("{:>12.4}", values[b][k]));
+            }
+            s.push('\n');
+        };
+        row("DF", &self.distribution_factors);
+        row("CO", &self.carry_over_factors);
+        row("FEM", &self.fixed_end_moments);
+        for i in 0..self.steps.len() {
+            row(&format!("Bal {}", i + 1), &self.steps[i].balance);
+            row(&format!("CO {}", i + 1), &self.steps[i].carry_over);
+        }
+        row("M", &self.moments);
+        return s;
+    }
+}
+
+impl System {
+    /// The moment distribution method (Momentenausgleichsverfahren nach Cross) for continuous
+    /// beams and non-sway frames after first order theory.
+    ///
+    /// The points are held against translation, the normal forces do not shorten the beams. The
+    /// stiffnesses, carry-over factors and fixed end moments are taken from the local stiffness
+    /// matrices, so hinges and spring releases of the beams are included. The unbalanced moments
+    /// of all joints are distributed at once and carried over, until they are smaller than
+    /// `tolerance` times the largest fixed end moment or external moment. The final end moments
+    /// converge to the moments of the stiffness method.
+    ///
+    /// Rotational springs of the supports take their share at the joint. Springs between points,
+    /// constraints, rigid offsets and support displacements are not supported. Systems, whose
+    /// points can translate with hinges at all joints, are rejected.
+    pub fn moment_distribution(
+        &self,
+        loading: &SystemLoading,
+        tolerance: f64,
+    ) -> MomentDistributionResultSet {
+        if !self.get_springs().is_empty() || !self.get_constraints().is_empty() {
+            panic!("Federn und Zwangsbedingungen werden vom Momentenausgleich nicht unterstützt.");
+        }
+        if !loading.get_settled_supports().is_empty() {
+            panic!("Lagerverschiebungen werden vom Momentenausgleich nicht unterstützt.");
+        }
+        for i in 0..self.get_beams().len() {
+            let o = self.get_beam_global_offsets(i);
+            if o.iter().flatten().any(|&v| v != 0.0) {
+                panic!("Starre Stabenden werden vom Momentenausgleich nicht unterstützt.");
+            }
+        }
+        if self.is_sway() {
+            panic!("Das System ist verschieblich.");
+        }
+
+        let nb = self.get_beams().len();
+        let np = self.get_points().len();
+        let mut stiffness = vec![[0.0; 2]; nb];
+        let mut carry_over_factors = vec![[0.0; 2]; nb];
+        let mut fixed_end_moments = vec![[0.0; 2]; nb];
+        for b in 0..nb {
+            let beam = &self.get_beams()[b];
+            if !beam.is_active() {
+                continue;
+            }
+            let l = self.get_beam_lenght(b);
+            let lineload = loading.get_total_lineload_for_beam(b);
+            let (k, f) = beam.local_stiffness_and_load_first_order(l, Some(lineload));
+            // Gelenke hinterlassen numerisch kleine Reste
+            let eps = 1e-12 * 4.0 * beam.get_emodul() * beam.get_ftm() / l;
+            for (e, (i, j)) in [(2, 5), (5, 2)].into_iter().enumerate() {
+                if k[(i, i)] > eps {
+                    stiffness[b][e] = k[(i, i)];
+                    carry_over_factors[b][e] = k[(j, i)] / k[(i, i)];
+                }
+                fixed_end_moments[b][e] = f[i];
+            }
+        }
+
+        // Drehsteifigkeiten der Knoten aus den Stabenden und den Drehfedern der Lager
+        let mut total = vec![0.0; np];
+        let mut springs = vec![0.0; np];
+        let mut fixed = vec![false; np];
+        for b in 0..nb {
+            total[self.get_beam_from_point(b)] += stiffness[b][0];
+            total[self.get_beam_to_point(b)] += stiffness[b][1];
+        }
+        for i in 0..self.get_supports().len() {
+            let sup = &self.get_supports()[i];
+            let p = self.get_support_points()[i];
+            if sup.is_fixed(2) {
+                fixed[p] = true;
+            } else if sup.has_spring(2) {
+                springs[p] += sup.get_feder()[2];
+            }
+        }
+        for p in 0..np {
+            total[p] += springs[p];
+        }
+        let balanced: Vec<bool> = (0..np).map(|p| !fixed[p] && total[p] > 0.0).collect();
+        let mut distribution_factors = vec![[0.0; 2]; nb];
+        for b in 0..nb {
+            let points = [self.get_beam_from_point(b), self.get_beam_to_point(b)];
+            for e in 0..2 {
+                if balanced[points[e]] {
+                    distribution_factors[b][e] = stiffness[b][e] / total[points[e]];
+                }
+            }
+        }
+
+        let mut external = vec![0.0; np];
+        for i in 0..loading.get_static_loads().len() {
+            external[loading.get_static_load_points()[i]] +=
+                loading.get_static_loads()[i].get_loading()[2];
+        }
+        let scale = fixed_end_moments
+            .iter()
+            .flatten()
+            .chain(external.iter())
+            .fold(0.0_f64, |m, v| m.max(v.abs()));
+        let scale = if scale > 0.0 { scale } else { 1.0 };
+
+        let mut moments = fixed_end_moments.clone();
+        let mut rotations = vec![0.0; np];
+        let mut steps = Vec::new();
+        let mut converged = false;
+        loop {
+            // Unausgeglichene Momente der Knoten mit den Momenten der Drehfedern
+            let mut unbalanced: Vec<f64> = (0..np)
+                .map(|p| springs[p] * rotations[p] - external[p])
+                .collect();
+            for b in 0..nb {
+                unbalanced[self.get_beam_from_point(b)] += moments[b][0];
+                unbalanced[self.get_beam_to_point(b)] += moments[b][1];
+            }
+            let max = (0..np)
+                .filter(|&p| balanced[p])
+                .fold(0.0_f64, |m, p| m.max(unbalanced[p].abs()));
+            if max <= tolerance * scale {
+                converged = true;
+                break;
+            }
+            if steps.len() == MAX_ITERATIONS {
+                break;
+            }
+
+            for p in 0..np {
+                if balanced[p] {
+                    rotations[p] -= unbalanced[p] / total[p];
+                }
+            }
+            let mut balance = vec![[0.0; 2]; nb];
+            let mut carry_over = vec![[0.0; 2]; nb];
+            for b in 0..nb {
+                let points = [self.get_beam_from_point(b), self.get_beam_to_point(b)];
+                for e in 0..2 {
+                    balance[b][e] = -distribution_factors[b][e] * unbalanced[points[e]];
+                }
+                for e in 0..2 {
+                    carry_over[b][1 - e] = carry_over_factors[b][e] * balance[b][e];
+                }
+                for e in 0..2 {
+                    moments[b][e] += balance[b][e] + carry_over[b][e];
+                }
+            }
+            steps.push(MomentDistributionStep {
+                balance,
+                carry_over,
+            });
+        }
+
+        MomentDistributionResultSet {
+            distribution_factors,
+            carry_over_factors,
+            fixed_end_moments,
+            steps,
+            moments,
+            rotations,
+            converged,
+        }
+    }
+
+    /// Ob das Gelenksystem mit Gelenken an allen Knoten und dehnstarren Stäben beweglich ist.
+    fn is_sway(&self) -> bool {
+        let np = self.get_points().len();
+        let mut rows: Vec<[(usize, f64); 4]> = Vec::new();
+        for b in 0..self.get_beams().len() {
+            let beam = &self.get_beams()[b];
+            let released = (beam.get_dofs()[0] && beam.get_dofstiffness()[0] == 0.0)
+                || (beam.get_dofs()[3] && beam.get_dofstiffness()[3] == 0.0);
+            if !beam.is_active() || released {
+                continue;
+            }
+            let from = self.get_beam_from_point(b);
+            let to = self.get_beam_to_point(b);
+            // Die Stabachse in Richtung der Freiheitsgrade, deren y-Achse zeigt nach unten
+            let (c, s) = (self.get_beam_alpha(b).cos(), -self.get_beam_alpha(b).sin());
+            rows.push([
+                (2 * from, -c),
+                (2 * from + 1, -s),
+                (2 * to, c),
+                (2 * to + 1, s),
+            ]);
+        }
+        for i in 0..self.get_supports().len() {
+            let sup = &self.get_supports()[i];
+            let p = self.get_support_points()[i];
+            // Wie in `dof_transformation` wirken die festen Lager in globaler Richtung
+            for j in 0..2 {
+                if sup.is_fixed(j) {
+                    rows.push([(2 * p + j, 1.0), (0, 0.0), (0, 0.0), (0, 0.0)]);
+                }
+            }
+        }
+        if rows.len() < 2 * np {
+            return true;
+        }
+        let mut a = MatrixDxD::zeros(rows.len(), 2 * np);
+        for i in 0..rows.len() {
+            for &(j, v) in &rows[i] {
+                a[(i, j)] += v;
+            }
+        }
+        return a.rank(1e-9) < 2 * np;
+    }
+}
@@ -0,0 +1,198 @@
+use crate::stiffness::influence::{InfluenceLine, InfluenceQuantity};
+use crate::stiffness::system::*;
+
+/// A load train of downward axle loads, e.g. a crane or a vehicle, optionally with a uniformly
+/// distributed load.
+#[derive(Clone)]
+pub struct LoadTrain {
+    axles: Vec<[f64; 2]>,
+    udl: f64,
+    udl_length: f64,
+}
+
+impl LoadTrain {
+    /// The axle loads with the spacings between consecutive axles, starting at the head of the
+    /// train. `spacings` has one entry less than `loads`.
+    pub fn new(loads: Vec<f64>, spacings: Vec<f64>) -> Self {
+        let mut axles = Vec::new();
+        let mut offset = 0.0;
+        for i in 0..loads.len() {
+            if i > 0 {
+                offset += spacings[i - 1];
+            }
+            axles.push([offset, loads[i]]);
+        }
+        LoadTrain {
+            axles,
+            udl: 0.0,
+            udl_length: 0.0,
+        }
+    }
+    /// A uniformly distributed load per length of the path, acting from the head over the given
+    /// length behind it.
+    pub fn with_udl(mut self, load: f64, length: f64) -> Self {
+        self.udl = load;
+        self.udl_length = length;
+        self
+    }
+    /// The axles as `[offset behind the head, load]`.
+    pub fn get_axles(&self) -> &[[f64; 2]] {
+        &self.axles
+    }
+    pub fn get_udl(&self) -> f64 {
+        self.udl
+    }
+    pub fn get_udl_length(&self) -> f64 {
+        self.udl_length
+    }
+    /// The length from the head to the last axle or the end of the distributed load.
+    pub fn get_length(&self) -> f64 {
+        let axles = self.axles.iter().map(|a| a[0]).fold(0.0, f64::max);
+        if self.udl != 0.0 {
+            axles.max(self.udl_length)
+        } else {
+            axles
+        }
+    }
+
+    /// The value of the quantity with the head of the train at the position along the load path.
+    pub fn evaluate(&self, line: &InfluenceLine, head: f64) -> f64 {
+        let mut res = 0.0;
+        for a in &self.axles {
+            res += a[1] * line.get_value_at(head - a[0]);
+        }
+        if self.udl != 0.0 {
+            res += self.udl * line.get_integral(head - self.udl_length, head);
+        }
+        return res;
+    }
+}
+
+/// The extreme values of one quantity and the positions of the head of the train along the path,
+/// which produce them.
+pub struct MovingLoadExtreme {
+    quantity: InfluenceQuantity,
+    min: f64,
+    min_position: f64,
+    max: f64,
+    max_position: f64,
+}
+
+impl MovingLoadExtreme {
+    pub fn get_quantity(&self) -> InfluenceQuantity {
+        self.quantity
+    }
+    pub fn get_min(&self) -> f64 {
+        self.min
+    }
+    pub fn get_min_position(&self) -> f64 {
+        self.min_position
+    }
+    pub fn get_max(&self) -> f64 {
+        self.max
+    }
+    pub fn get_max_position(&self) -> f64 {
+        self.max_position
+    }
+}
+
+pub struct MovingLoadResultSet {
+    lines: Vec<InfluenceLine>,
+    positions: Vec<f64>,
+    values: Vec<Vec<f64>>,
+    extremes: Vec<MovingLoadExtreme>,
+}
+
+impl MovingLoadResultSet {
+    /// The influence lines of the quantities.
+    pub fn get_influence_lines(&self) -> &[InfluenceLine] {
+        &self.lines
+    }
+    /// The positions of the head of the train along the load path.
+    pub fn get_positions(&self) -> &[f64] {
+        &self.positions
+    }
+    /// The values of every quantity at every position of the train.
+    pub fn get_values(&self) -> &[Vec<f64>] {
+        &self.values
+    }
+    pub fn get_extremes(&self) -> &[MovingLoadExtreme] {
+        &self.extremes
+    }
+}
+
+impl System {
+    /// Moves the load train in steps along the beams of the path, from entering the path with
+    /// its head until it has left it completely, and reports the extreme values of the quantities.
+    ///
+    /// The values are evaluated with the influence lines of a downward unit load using first
+    /// order theory, see `influence_lines`. Between the `samples` of every beam the influence
+    /// lines are linear. Besides the steps the train is placed with every axle on every sample
+    /// and just right of it, so the extremes of the axle loads are not missed at the sections.
+    ///
+    /// Panics if `step` is not positive.
+    pub fn moving_load(
+        &self,
+        train: &LoadTrain,
+        quantities: &[InfluenceQuantity],
+        path: &[usize],
+        samples: usize,
+        step: f64,
+    ) -> MovingLoadResultSet {
+        let lines =
+            self.influence_lines(quantities, path, samples, &StaticLoad::new(0.0, 1.0, 0.0));
+        if step.is_nan() || step <= 0.0 {
+            panic!("Die Schrittweite der Laststellungen muss positiv sein.");
+        }
+        let end = lines.first().map(|l| l.get_path_length()).unwrap_or(0.0) + train.get_length();
+        let steps = (end / step).ceil() as usize;
+        let mut positions: Vec<f64> = (0..=steps).map(|i| (i as f64 * step).min(end)).collect();
+        // Die Achsen auf den Stützstellen, an einem Sprung auch auf dessen rechter Seite
+        let eps = 1e-9 * end;
+        if let Some(line) = lines.first() {
+            for &s in line.get_stations() {
+                for a in train.get_axles() {
+                    for p in [s + a[0], s + a[0] + eps] {
+                        if p <= end {
+                            positions.push(p);
+                        }
+                    }
+                }
+            }
+        }
+        positions.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        positions.dedup();
+
+        let mut values = Vec::new();
+        let mut extremes = Vec::new();
+        for line in &lines {
+            let v: Vec<f64> = positions.iter().map(|&p| train.evaluate(line, p)).collect();
+            let mut e = MovingLoadExtreme {
+                quantity: line.get_quantity(),
+                min: f64::INFINITY,
+                min_position: 0.0,
+                max: f64::NEG_INFINITY,
+                max_position: 0.0,
+            };
+            for i in 0..v.len() {
+                if v[i] < e.min {
+                    e.min = v[i];
+                    e.min_position = positions[i];
+                }
+                if v[i] > e.max {
+                    e.max = v[i];
+                    e.max_position = positions[i];
+                }
+            }
+            values.push(v);
+            extremes.push(e);
+        }
+
+        MovingLoadResultSet {
+            lines,
+            positions,
+            values,
+            extremes,
+        }
+    }
+}
@@ -0,0 +1,380 @@
+use crate::stiffness::direct_stiffness::BeamResultSet;
+use crate::stiffness::system::*;
+use nalgebra::DVector;
+
+type VectorD = DVector<f64>;
+
+/// A plastic hinge in the order of its formation.
+pub struct PlasticHinge {
+    beam: usize,
+    position: f64,
+    load_factor: f64,
+    moment: f64,
+    normal_force: f64,
+}
+
+impl PlasticHinge {
+    pub fn get_beam(&self) -> usize {
+        self.beam
+    }
+    /// The position on the beam measured from the start face.
+    pub fn get_position(&self) -> f64 {
+        self.position
+    }
+    /// The load factor of the variable loading, at which the hinge formed.
+    pub fn get_load_factor(&self) -> f64 {
+        self.load_factor
+    }
+    pub fn get_moment(&self) -> f64 {
+        self.moment
+    }
+    pub fn get_normal_force(&self) -> f64 {
+        self.normal_force
+    }
+}
+
+/// The state of the system after an event.
+pub struct PlasticStep {
+    load_factor: f64,
+    displacements: Vec<[f64; 3]>,
+    hinges: usize,
+}
+
+impl PlasticStep {
+    pub fn get_load_factor(&self) -> f64 {
+        self.load_factor
+    }
+    /// The displacements x_1, x_2, phi_3 of the points of the system.
+    pub fn get_displacements(&self) -> &[[f64; 3]] {
+        &self.displacements
+    }
+    /// The number of hinges formed up to this step, see `PlasticResultSet::get_hinges`.
+    pub fn get_hinge_count(&self) -> usize {
+        self.hinges
+    }
+}
+
+pub struct PlasticResultSet {
+    load_factor: f64,
+    mechanism: bool,
+    hinges: Vec<PlasticHinge>,
+    steps: Vec<PlasticStep>,
+}
+
+impl PlasticResultSet {
+    /// The collapse load factor of the variable loading, infinite without a mechanism.
+    pub fn get_load_factor(&self) -> f64 {
+        self.load_factor
+    }
+    /// Whether the hinges have formed a mechanism.
+    pub fn is_mechanism(&self) -> bool {
+        self.mechanism
+    }
+    pub fn get_hinges(&self) -> &[PlasticHinge] {
+        &self.hinges
+    }
+    pub(crate) fn into_hinges(self) -> Vec<PlasticHinge> {
+        self.hinges
+    }
+    /// The load factor and the displacements after the constant loading and after every event.
+    pub fn get_steps(&self) -> &[PlasticStep] {
+        &self.steps
+    }
+}
+
+/// Ein Kontrollschnitt am Ende eines Teilstabes.
+struct Section {
+    beam: usize,
+    position: f64,
+    part: usize,
+    end: usize,
+    moment: f64,
+    normal_force: f64,
+    hinge: bool,
+}
+
+impl System {
+    /// The elastic-plastic analysis after the event-to-event method with first order theory.
+    ///
+    /// The `constant` loading is applied first, then the `variable` loading is increased until a
+    /// mechanism forms. Every beam is checked at its ends and at `samples - 1` points in the span.
+    /// A hinge forms, when `|M| = M_pl` or `|M| / M_pl + |N| / N_pl = 1` with the plastic values of
+    /// the `Crosssection`, it is modelled as moment release carrying its plastic moment. Unloading
+    /// of the hinges is not considered. The system is a mechanism, if its stiffness matrix is
+    /// singular.
+    pub fn plastic_hinge_analysis(
+        &self,
+        constant: &SystemLoading,
+        variable: &SystemLoading,
+        samples: usize,
+    ) -> PlasticResultSet {
+        let lengths: Vec<f64> = (0..self.get_beams().len())
+            .map(|b| self.get_beam_lenght(b))
+            .collect();
+        let positions: Vec<Vec<f64>> = lengths
+            .iter()
+            .map(|&l| {
+                (1..samples)
+                    .map(|s| l * s as f64 / samples as f64)
+                    .collect()
+            })
+            .collect();
+        let (mut sys, parts) = self.subdivide(&positions);
+        let constant = constant.subdivide(&lengths, &parts);
+        let variable = variable.subdivide(&lengths, &parts);
+
+        let mut sections = Vec::new();
+        for b in 0..parts.len() {
+            let p = &parts[b];
+            sections.push(Section::new(b, 0.0, p[0].0, 0));
+            for k in 0..p.len() {
+                let x1 = if k + 1 < p.len() {
+                    p[k + 1].1
+                } else {
+                    lengths[b]
+                };
+                sections.push(Section::new(b, x1, p[k].0, 1));
+            }
+        }
+
+        let points = self.get_points().len();
+        let mut u = VectorD::zeros(sys.get_points().len() * 3);
+        let mut load_factor = 0.0;
+        let mut mechanism = false;
+        let mut hinges = Vec::new();
+        let mut steps = Vec::new();
+
+        // Erst die ständige Belastung bis zum Faktor 1, dann die veränderliche ohne Grenze
+        for (loading, target) in [(&constant, Some(1.0)), (&variable, None)] {
+            let mut factor = 0.0;
+            loop {
+                let (du, res) = match sys.try_solve_first_order(loading) {
+                    Some(t) => t,
+                    None => {
+                        mechanism = true;
+                        break;
+                    }
+                };
+                // Der kleinste Laststeigerungsfaktor bis zum nächsten Gelenk
+                let mut increments = Vec::new();
+                let mut step = f64::INFINITY;
+                for s in sections.iter() {
+                    let (dm, dn) = s.increment(&res);
+                    increments.push((dm, dn));
+                    if s.hinge {
+                        continue;
+                    }
+                    let b = &sys.get_beams()[s.part];
+                    if let Some(mpl) = b.get_plastic_moment() {
+                        let d = s.yield_increment(dm, dn, mpl, b.get_plastic_normal_force());
+                        step = step.min(d);
+                    }
+                }
+                let last = match target {
+                    Some(t) => factor + step >= t,
+                    None => false,
+                };
+                if last {
+                    step = target.unwrap() - factor;
+                }
+                if step == f64::INFINITY {
+                    break;
+                }
+                factor += step;
+                u += step * du;
+                for (s, (dm, dn)) in sections.iter_mut().zip(increments.iter()) {
+                    s.moment += step * dm;
+                    s.normal_force += step * dn;
+                }
+                if target.is_none() {
+                    load_factor = factor;
+                }
+                if last {
+                    break;
+                }
+
+                // Gelenke aller Schnitte, die die Fließbedingung erreichen
+                for s in sections.iter_mut() {
+                    let b = sys.get_beams()[s.part];
+                    if s.hinge || b.get_plastic_moment().is_none() {
+                        continue;
+                    }
+                    let mpl = b.get_plastic_moment().unwrap();
+                    if s.utilisation(mpl, b.get_plastic_normal_force()) > 1.0 - 1e-9 {
+                        s.hinge = true;
+                        // Der letzte biegesteife Anschluss eines Knotens bleibt, das Gelenk
+                        // liegt dann bereits in den anderen Stäben
+                        let point = if s.end == 0 {
+                            sys.get_beam_from_point(s.part)
+                        } else {
+                            sys.get_beam_to_point(s.part)
+                        };
+                        if sys.rotational_connections(point) <= 1 {
+                            continue;
+                        }
+                        sys.get_beams_mut()[s.part].set_release(2 + 3 * s.end, 0.0);
+                        hinges.push(PlasticHinge {
+                            beam: s.beam,
+                            position: s.position,
+                            load_factor: if target.is_none() { factor } else { 0.0 },
+                            moment: s.moment,
+                            normal_force: s.normal_force,
+                        });
+                    }
+                }
+                steps.push(PlasticStep {
+                    load_factor: if target.is_none() { factor } else { 0.0 },
+                    displacements: (0..points)
+                        .map(|p| [u[p * 3], u[p * 3 + 1], u[p * 3 + 2]])
+                        .collect(),
+                    hinges: hinges.len(),
+                });
+            }
+            if mechanism {
+                break;
+            }
+            if target.is_some() {
+                steps.push(PlasticStep {
+                    load_factor: 0.0,
+                    displacements: (0..points)
+                        .map(|p| [u[p * 3], u[p * 3 + 1], u[p * 3 + 2]])
+                        .collect(),
+                    hinges: hinges.len(),
+                });
+            }
+        }
+
+        PlasticResultSet {
+            load_factor: if mechanism {
+                load_factor
+            } else {
+                f64::INFINITY
+            },
+            mechanism,
+            hinges,
+            steps,
+        }
+    }
+
+    /// Die Anzahl der Stabenden, Lager, Federn und Zwangsbedingungen, die die Verdrehung des
+    /// Punktes halten.
+    fn rotational_connections(&self, point: usize) -> usize {
+        let mut n = 0;
+        for i in 0..self.get_beams().len() {
+            let b = &self.get_beams()[i];
+            if !b.is_active() {
+                continue;
+            }
+            if self.get_beam_from_point(i) == point && !b.get_dofs()[2] {
+                n += 1;
+            }
+            if self.get_beam_to_point(i) == point && !b.get_dofs()[5] {
+                n += 1;
+            }
+        }
+        for i in 0..self.get_supports().len() {
+            let sup = &self.get_supports()[i];
+            if self.get_support_points()[i] == point
+                && (sup.is_fixed(2) || (sup.has_spring(2) && sup.get_feder()[2] != 0.0))
+            {
+                n += 1;
+            }
+        }
+        for i in 0..self.get_springs().len() {
+            if self.get_spring_points()[i].contains(&point)
+                && self.get_springs()[i].get_stiffness()[2] != 0.0
+            {
+                n += 1;
+            }
+        }
+        for c in self.get_constraints() {
+            if c.get_terms().iter().any(|&(p, d, _)| p == point && d == 2) {
+                n += 1;
+            }
+        }
+        return n;
+    }
+
+    /// Löst das System nach Theorie I. Ordnung, None bei einer kinematischen Kette.
+    pub(crate) fn try_solve_first_order(
+        &self,
+        loading: &SystemLoading,
+    ) -> Option<(VectorD, BeamResultSet)> {
+        let steif = self.global_stiffness_first_order();
+        let last = self.load_vector_first_order(loading);
+        let (k, f, t, u0) = self.reduced_system(loading, &steif, &last);
+        let g = k.clone().cholesky()?;
+        // Verschwindende Pivotelemente durch Rundungsfehler
+        let l = g.l();
+        for i in 0..k.nrows() {
+            if l[(i, i)] * l[(i, i)] < 1e-10 * k[(i, i)].abs() {
+                return None;
+            }
+        }
+        let u = t * g.solve(&f) + u0;
+        let res = self.beam_results_first_order(loading, &u);
+        return Some((u, res));
+    }
+}
+
+impl Section {
+    fn new(beam: usize, position: f64, part: usize, end: usize) -> Self {
+        Section {
+            beam,
+            position,
+            part,
+            end,
+            moment: 0.0,
+            normal_force: 0.0,
+            hinge: false,
+        }
+    }
+
+    /// Die Zuwächse von M und N aus der Lösung des Laststeigerungsschritts.
+    fn increment(&self, res: &BeamResultSet) -> (f64, f64) {
+        let r = &res.get_results()[self.part];
+        let x = if self.end == 0 {
+            0.0
+        } else {
+            r.get_beam_lenght()
+        };
+        let i = r.get_internals_at(x);
+        return (i[2], i[0]);
+    }
+
+    fn utilisation(&self, mpl: f64, npl: Option<f64>) -> f64 {
+        let n = match npl {
+            Some(npl) => self.normal_force.abs() / npl,
+            None => 0.0,
+        };
+        return self.moment.abs() / mpl + n;
+    }
+
+    /// Der kleinste positive Faktor, bei dem die Fließbedingung erreicht wird.
+    fn yield_increment(&self, dm: f64, dn: f64, mpl: f64, npl: Option<f64>) -> f64 {
+        let (a, b) = match npl {
+            Some(npl) => (dn / npl, self.normal_force / npl),
+            None => (0.0, 0.0),
+        };
+        let mut res = f64::INFINITY;
+        for sm in [-1.0, 1.0] {
+            for sn in [-1.0, 1.0] {
+                let denominator = sm * dm / mpl + sn * a;
+                if denominator <= 0.0 {
+                    continue;
+                }
+                let step = (1.0 - sm * self.moment / mpl - sn * b) / denominator;
+                if step < 0.0 {
+                    continue;
+                }
+                let m = self.moment + step * dm;
+                let n = b + step * a;
+                let f = m.abs() / mpl + n.abs();
+                if (f - 1.0).abs() < 1e-9 {
+                    res = res.min(step);
+                }
+            }
+        }
+        return res;
+    }
+}
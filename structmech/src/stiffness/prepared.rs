@@ -0,0 +1,76 @@
+use crate::stiffness::direct_stiffness::BeamResultSet;
+use crate::stiffness::system::*;
+use nalgebra::linalg::Cholesky;
+use nalgebra::DVector;
+use nalgebra::Dynamic;
+use nalgebra::OMatrix;
+
+type MatrixDxD = OMatrix<f64, Dynamic, Dynamic>;
+type VectorD = DVector<f64>;
+
+/// A system with its assembled and factorised first order stiffness matrix, which solves many
+/// loadings against one Cholesky factorisation.
+///
+/// The system has to be linear, so iterative solvers (unilateral, cables, semi-rigid) cannot use
+/// it.
+pub struct PreparedSystem {
+    system: System,
+    steif: MatrixDxD,
+    t: MatrixDxD,
+    u0: VectorD,
+    factor: Cholesky<f64, Dynamic>,
+}
+
+impl System {
+    /// Assembles and factorises the first order stiffness matrix once, see `PreparedSystem`.
+    pub fn prepare_first_order(&self) -> PreparedSystem {
+        let empty = SystemLoading::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        let steif = self.global_stiffness_first_order();
+
+        let (t, u0) = self.dof_transformation(&empty);
+        let k = t.transpose() * &steif * &t;
+        let factor = match k.cholesky() {
+            Some(t) => t,
+            None => panic!("Matrix nicht positiv definit."),
+        };
+        PreparedSystem {
+            system: self.clone(),
+            steif,
+            t,
+            u0,
+            factor,
+        }
+    }
+}
+
+impl PreparedSystem {
+    pub fn get_system(&self) -> &System {
+        &self.system
+    }
+
+    /// Solves the loading with the stored factorisation, the result equals
+    /// `matrix_stiffness_method_first_order`.
+    pub fn solve(&self, loading: &SystemLoading) -> BeamResultSet {
+        let sys = &self.system;
+        let last = sys.load_vector_first_order(loading);
+
+        // Lagerverschiebungen ändern nur die vorgegebenen Verschiebungen u_0
+        let u0 = if loading.get_settled_supports().is_empty() {
+            self.u0.clone()
+        } else {
+            sys.dof_transformation(loading).1
+        };
+        let f = self.t.transpose() * (&last - &self.steif * &u0);
+        let result = &self.t * self.factor.solve(&f) + u0;
+        let constraint_forces = sys.constraint_forces(&(&self.steif * &result - &last));
+
+        return sys
+            .beam_results_first_order(loading, &result)
+            .with_constraint_forces(constraint_forces);
+    }
+
+    /// Solves every loading, see `solve`.
+    pub fn solve_all(&self, loadings: &[SystemLoading]) -> Vec<BeamResultSet> {
+        loadings.iter().map(|l| self.solve(l)).collect()
+    }
+}
@@ -0,0 +1,176 @@
+use crate::stiffness::modal::MassMatrix;
+use crate::stiffness::plastic::PlasticHinge;
+use crate::stiffness::system::*;
+
+/// The distribution of the lateral loads over the height.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LateralLoadPattern {
+    /// Proportional to the masses of the points.
+    Uniform,
+    /// Proportional to the masses times the height above the lowest point.
+    Triangular,
+    /// Proportional to the masses times the horizontal displacements of the mode with the given
+    /// index, see `modal_analysis`.
+    Modal(usize),
+}
+
+/// A point of the capacity curve.
+pub struct PushoverStep {
+    base_shear: f64,
+    roof_displacement: f64,
+    hinges: usize,
+}
+
+impl PushoverStep {
+    pub fn get_base_shear(&self) -> f64 {
+        self.base_shear
+    }
+    /// The horizontal displacement of the control point.
+    pub fn get_roof_displacement(&self) -> f64 {
+        self.roof_displacement
+    }
+}
+
+pub struct PushoverResultSet {
+    pattern: Vec<(usize, f64)>,
+    steps: Vec<PushoverStep>,
+    hinges: Vec<PlasticHinge>,
+    mechanism: bool,
+}
+
+impl PushoverResultSet {
+    /// The lateral loads `(point, force)` of the pattern, normalised to a unit base shear.
+    pub fn get_pattern(&self) -> &[(usize, f64)] {
+        &self.pattern
+    }
+    /// The state after the gravity loading and after every hinge.
+    pub fn get_steps(&self) -> &[PushoverStep] {
+        &self.steps
+    }
+    /// The capacity curve as points `[roof displacement, base shear]`.
+    pub fn get_capacity_curve(&self) -> Vec<[f64; 2]> {
+        self.steps
+            .iter()
+            .map(|s| [s.roof_displacement, s.base_shear])
+            .collect()
+    }
+    /// All hinges in the order of their formation, the load factor is the base shear.
+    pub fn get_hinges(&self) -> &[PlasticHinge] {
+        &self.hinges
+    }
+    /// The hinges, which have formed up to the step.
+    pub fn get_hinges_at(&self, step: usize) -> &[PlasticHinge] {
+        &self.hinges[..self.steps[step].hinges]
+    }
+    /// The base shear at collapse, infinite without a mechanism.
+    pub fn get_capacity(&self) -> f64 {
+        if self.mechanism {
+            self.steps[self.steps.len() - 1].base_shear
+        } else {
+            f64::INFINITY
+        }
+    }
+    pub fn is_mechanism(&self) -> bool {
+        self.mechanism
+    }
+}
+
+impl System {
+    /// The lateral loads of the pattern in the global x_1 direction with a sum of 1.
+    ///
+    /// The masses are taken from the lumped mass matrix. Points, which are fixed horizontally by
+    /// a support, are not loaded.
+    pub fn lateral_loading(&self, pattern: LateralLoadPattern) -> SystemLoading {
+        let m = self.mass_matrix(MassMatrix::Lumped);
+        let points = self.get_points();
+        let base = points.iter().map(|p| p.y).fold(f64::MAX, f64::min);
+        let shape = match pattern {
+            LateralLoadPattern::Modal(mode) => {
+                let modal = self.modal_analysis(MassMatrix::Lumped, mode + 1);
+                match modal.get_modes().get(mode) {
+                    Some(t) => t.get_shape().iter().map(|s| s[0]).collect(),
+                    None => panic!("Die Eigenform {} existiert nicht.", mode),
+                }
+            }
+            // Die Höhe über dem tiefsten Punkt
+            LateralLoadPattern::Triangular => points.iter().map(|p| p.y - base).collect(),
+            LateralLoadPattern::Uniform => vec![1.0; points.len()],
+        };
+
+        let mut fixed = vec![false; points.len()];
+        for i in 0..self.get_supports().len() {
+            if self.get_supports()[i].is_fixed(0) {
+                fixed[self.get_support_points()[i]] = true;
+            }
+        }
+        let forces: Vec<f64> = (0..points.len())
+            .map(|p| {
+                if fixed[p] {
+                    0.0
+                } else {
+                    m[(p * 3, p * 3)] * shape[p]
+                }
+            })
+            .collect();
+        let sum: f64 = forces.iter().sum();
+        if sum.abs() < 1e-12 {
+            panic!("Das Lastmuster hat keine resultierende Horizontalkraft.");
+        }
+
+        let mut loaded = Vec::new();
+        let mut loads = Vec::new();
+        for p in 0..points.len() {
+            if forces[p] != 0.0 {
+                loaded.push(p);
+                loads.push(StaticLoad::new(forces[p] / sum, 0.0, 0.0));
+            }
+        }
+        return SystemLoading::new(loaded, loads, Vec::new(), Vec::new());
+    }
+
+    /// The nonlinear static pushover analysis with plastic hinges, see `plastic_hinge_analysis`.
+    ///
+    /// The `gravity` loading is applied first, then the lateral loading of the pattern is
+    /// increased until a mechanism forms. The base shear is the load factor of the lateral
+    /// loading, the roof displacement the horizontal displacement of the `control` point.
+    pub fn pushover(
+        &self,
+        gravity: &SystemLoading,
+        pattern: LateralLoadPattern,
+        control: usize,
+        samples: usize,
+    ) -> PushoverResultSet {
+        let lateral = self.lateral_loading(pattern);
+        let res = self.plastic_hinge_analysis(gravity, &lateral, samples);
+
+        // Die Kurve beginnt mit dem Zustand nach der Vertikallast
+        let steps = res.get_steps();
+        let first = steps
+            .iter()
+            .rposition(|s| s.get_load_factor() == 0.0)
+            .unwrap_or(0);
+        let steps = steps[first..]
+            .iter()
+            .map(|s| PushoverStep {
+                base_shear: s.get_load_factor(),
+                roof_displacement: s.get_displacements()[control][0],
+                hinges: s.get_hinge_count(),
+            })
+            .collect();
+
+        let mut pattern = Vec::new();
+        for i in 0..lateral.get_static_loads().len() {
+            pattern.push((
+                lateral.get_static_load_points()[i],
+                lateral.get_static_loads()[i].get_loading()[0],
+            ));
+        }
+        let mechanism = res.is_mechanism();
+        PushoverResultSet {
+            pattern,
+            steps,
+            hinges: res.into_hinges(),
+            mechanism,
+        }
+    }
+}
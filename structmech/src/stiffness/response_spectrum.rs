@@ -0,0 +1,322 @@
+use crate::stiffness::direct_stiffness::BeamResultSet;
+use crate::stiffness::envelope::{BeamEnvelope, Envelope};
+use crate::stiffness::modal::MassMatrix;
+use crate::stiffness::system::*;
+use nalgebra::DVector;
+
+type VectorD = DVector<f64>;
+
+/// The ground types A to E of EN 1998-1 table 3.1.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GroundType {
+    A,
+    B,
+    C,
+    D,
+    E,
+}
+
+/// A design spectrum of the horizontal acceleration over the period.
+#[derive(Clone, PartialEq, Debug)]
+pub enum ResponseSpectrum {
+    /// Points `[T, S_d]` with increasing period, linear in between and constant outside.
+    Tabulated(Vec<[f64; 2]>),
+    /// The design spectrum of EN 1998-1 3.2.2.5.
+    En1998 {
+        ag: f64,
+        s: f64,
+        tb: f64,
+        tc: f64,
+        td: f64,
+        q: f64,
+        beta: f64,
+    },
+}
+
+impl ResponseSpectrum {
+    /// The design spectrum of EN 1998-1 with the recommended values of table 3.2 (type 1) or
+    /// table 3.3 (type 2), the design ground acceleration `ag`, the behaviour factor `q` and the
+    /// lower bound factor beta = 0.2.
+    pub fn en1998(ag: f64, ground: GroundType, type_2: bool, q: f64) -> Self {
+        let [s, tb, tc, td] = match (type_2, ground) {
+            (false, GroundType::A) => [1.0, 0.15, 0.4, 2.0],
+            (false, GroundType::B) => [1.2, 0.15, 0.5, 2.0],
+            (false, GroundType::C) => [1.15, 0.2, 0.6, 2.0],
+            (false, GroundType::D) => [1.35, 0.2, 0.8, 2.0],
+            (false, GroundType::E) => [1.4, 0.15, 0.5, 2.0],
+            (true, GroundType::A) => [1.0, 0.05, 0.25, 1.2],
+            (true, GroundType::B) => [1.35, 0.05, 0.25, 1.2],
+            (true, GroundType::C) => [1.5, 0.1, 0.25, 1.2],
+            (true, GroundType::D) => [1.8, 0.1, 0.3, 1.2],
+            (true, GroundType::E) => [1.6, 0.05, 0.25, 1.2],
+        };
+        ResponseSpectrum::En1998 {
+            ag,
+            s,
+            tb,
+            tc,
+            td,
+            q,
+            beta: 0.2,
+        }
+    }
+
+    /// The design acceleration S_d(T).
+    pub fn get_acceleration(&self, period: f64) -> f64 {
+        match self {
+            ResponseSpectrum::Tabulated(points) => {
+                if points.is_empty() {
+                    return 0.0;
+                }
+                if period <= points[0][0] {
+                    return points[0][1];
+                }
+                for i in 0..points.len() - 1 {
+                    if period <= points[i + 1][0] {
+                        let r = (period - points[i][0]) / (points[i + 1][0] - points[i][0]);
+                        return points[i][1] * (1.0 - r) + points[i + 1][1] * r;
+                    }
+                }
+                return points[points.len() - 1][1];
+            }
+            &ResponseSpectrum::En1998 {
+                ag,
+                s,
+                tb,
+                tc,
+                td,
+                q,
+                beta,
+            } => {
+                let t = period;
+                if t <= tb {
+                    ag * s * (2.0 / 3.0 + t / tb * (2.5 / q - 2.0 / 3.0))
+                } else if t <= tc {
+                    ag * s * 2.5 / q
+                } else if t <= td {
+                    (ag * s * 2.5 / q * tc / t).max(beta * ag)
+                } else {
+                    (ag * s * 2.5 / q * tc * td / (t * t)).max(beta * ag)
+                }
+            }
+        }
+    }
+}
+
+/// The combination rule of the modal responses.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ModalCombination {
+    /// Square root of the sum of the squares.
+    Srss,
+    /// Complete quadratic combination with the given damping ratio of all modes.
+    Cqc(f64),
+}
+
+/// The contribution of one mode.
+pub struct ModalParticipation {
+    period: f64,
+    participation_factor: f64,
+    effective_mass: f64,
+    acceleration: f64,
+}
+
+impl ModalParticipation {
+    pub fn get_period(&self) -> f64 {
+        self.period
+    }
+    /// Gamma = phi^T M r of the mass normalised mode.
+    pub fn get_participation_factor(&self) -> f64 {
+        self.participation_factor
+    }
+    pub fn get_effective_mass(&self) -> f64 {
+        self.effective_mass
+    }
+    /// The design acceleration of the spectrum at the period.
+    pub fn get_acceleration(&self) -> f64 {
+        self.acceleration
+    }
+}
+
+pub struct ResponseSpectrumResultSet {
+    modes: Vec<ModalParticipation>,
+    total_mass: f64,
+    modal_results: Vec<BeamResultSet>,
+    displacements: Vec<[f64; 3]>,
+    reactions: Vec<[f64; 3]>,
+    envelope: Envelope,
+}
+
+impl ResponseSpectrumResultSet {
+    pub fn get_modes(&self) -> &[ModalParticipation] {
+        &self.modes
+    }
+    /// The mass in the direction of the excitation.
+    pub fn get_total_mass(&self) -> f64 {
+        self.total_mass
+    }
+    /// The sum of the effective masses of the modes divided by the total mass.
+    pub fn get_mass_ratio(&self) -> f64 {
+        self.modes.iter().map(|m| m.effective_mass).sum::<f64>() / self.total_mass
+    }
+    /// The peak response of every mode with its sign.
+    pub fn get_modal_results(&self) -> &[BeamResultSet] {
+        &self.modal_results
+    }
+    /// The combined displacements of the points.
+    pub fn get_displacements(&self) -> &[[f64; 3]] {
+        &self.displacements
+    }
+    /// The combined support reactions.
+    pub fn get_reactions(&self) -> &[[f64; 3]] {
+        &self.reactions
+    }
+    /// The combined internal forces and displacements along the beams as `+-` envelope.
+    pub fn get_envelope(&self) -> &Envelope {
+        &self.envelope
+    }
+}
+
+impl System {
+    /// The response spectrum analysis for an excitation in the global direction `direction`
+    /// (0 = x_1, 1 = x_2) with the lowest `modes` modes, see `modal_analysis`.
+    ///
+    /// The peak response of mode i is `Gamma_i S_d(T_i) / omega_i^2 phi_i`, its internal forces
+    /// are recovered from the displacements. The responses are combined with SRSS or CQC at
+    /// `samples + 1` positions of every beam.
+    pub fn response_spectrum(
+        &self,
+        spectrum: &ResponseSpectrum,
+        direction: usize,
+        mass: MassMatrix,
+        modes: usize,
+        combination: ModalCombination,
+        samples: usize,
+    ) -> ResponseSpectrumResultSet {
+        let empty = SystemLoading::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        let modal = self.modal_analysis(mass, modes);
+        let m = self.mass_matrix(mass);
+        let dofs = self.get_points().len() * 3;
+        let mut r = VectorD::zeros(dofs);
+        for p in 0..self.get_points().len() {
+            r[p * 3 + direction] = 1.0;
+        }
+        let mr = &m * &r;
+        let total_mass = r.dot(&mr);
+
+        let mut participation = Vec::new();
+        let mut omegas = Vec::new();
+        let mut modal_results = Vec::new();
+        let mut node_values: Vec<VectorD> = Vec::new();
+        let mut reaction_values: Vec<Vec<[f64; 3]>> = Vec::new();
+        for mode in modal.get_modes() {
+            let phi = VectorD::from_iterator(
+                dofs,
+                mode.get_shape().iter().flat_map(|p| p.iter().cloned()),
+            );
+            let gamma = phi.dot(&mr);
+            let omega = mode.get_angular_frequency();
+            let sa = spectrum.get_acceleration(mode.get_period());
+            let u = gamma * sa / (omega * omega) * &phi;
+            let res = self.beam_results_first_order(&empty, &u);
+            reaction_values.push(self.support_reactions(&empty, &res));
+            node_values.push(u);
+            modal_results.push(res);
+            omegas.push(omega);
+            participation.push(ModalParticipation {
+                period: mode.get_period(),
+                participation_factor: gamma,
+                effective_mass: gamma * gamma,
+                acceleration: sa,
+            });
+        }
+
+        // Korrelationskoeffizienten der Moden
+        let n = omegas.len();
+        let mut rho = vec![vec![0.0; n]; n];
+        for i in 0..n {
+            for j in 0..n {
+                rho[i][j] = match combination {
+                    ModalCombination::Srss => {
+                        if i == j {
+                            1.0
+                        } else {
+                            0.0
+                        }
+                    }
+                    ModalCombination::Cqc(zeta) => {
+                        let b = omegas[j] / omegas[i];
+                        8.0 * zeta * zeta * (1.0 + b) * b.powf(1.5)
+                            / ((1.0 - b * b).powi(2) + 4.0 * zeta * zeta * b * (1.0 + b).powi(2))
+                    }
+                };
+            }
+        }
+        let combine = |values: &[f64]| {
+            let mut s = 0.0;
+            for i in 0..n {
+                for j in 0..n {
+                    s += rho[i][j] * values[i] * values[j];
+                }
+            }
+            s.max(0.0).sqrt()
+        };
+
+        let displacements = (0..self.get_points().len())
+            .map(|p| {
+                let mut d = [0.0; 3];
+                for k in 0..3 {
+                    let v: Vec<f64> = node_values.iter().map(|u| u[p * 3 + k]).collect();
+                    d[k] = combine(&v);
+                }
+                d
+            })
+            .collect();
+        let reactions = (0..self.get_supports().len())
+            .map(|s| {
+                let mut d = [0.0; 3];
+                for k in 0..3 {
+                    let v: Vec<f64> = reaction_values.iter().map(|r| r[s][k]).collect();
+                    d[k] = combine(&v);
+                }
+                d
+            })
+            .collect();
+
+        let mut beams = Vec::new();
+        for b in 0..self.get_beams().len() {
+            let l = self.get_beam_lenght(b);
+            let positions: Vec<f64> = (0..=samples)
+                .map(|s| l * s as f64 / samples as f64)
+                .collect();
+            let mut max = Vec::new();
+            for &x in &positions {
+                let internals: Vec<[f64; 6]> = modal_results
+                    .iter()
+                    .map(|r| r.get_results()[b].get_internals_at(x))
+                    .collect();
+                let mut e = [0.0; 6];
+                for k in 0..6 {
+                    let v: Vec<f64> = internals.iter().map(|i| i[k]).collect();
+                    e[k] = combine(&v);
+                }
+                max.push(e);
+            }
+            let min = max.iter().map(|e| e.map(|v| -v)).collect();
+            let zeros = vec![[0; 6]; positions.len()];
+            beams.push(BeamEnvelope::new(positions, min, max, zeros.clone(), zeros));
+        }
+        let name = match combination {
+            ModalCombination::Srss => "SRSS",
+            ModalCombination::Cqc(_) => "CQC",
+        };
+
+        ResponseSpectrumResultSet {
+            modes: participation,
+            total_mass,
+            modal_results,
+            displacements,
+            reactions,
+            envelope: Envelope::from_beams(vec![name.to_string()], beams),
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use crate::stiffness::direct_stiffness::BeamResultSet;
+use crate::stiffness::system::*;
+
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-6;
+
+/// The rotation and moment demand of a semi-rigid connection.
+pub struct ConnectionResult {
+    beam: usize,
+    end: usize,
+    rotation: f64,
+    moment: f64,
+    stiffness: f64,
+}
+
+impl ConnectionResult {
+    pub fn get_beam(&self) -> usize {
+        self.beam
+    }
+    /// `0` for the start, `1` for the end of the beam.
+    pub fn get_end(&self) -> usize {
+        self.end
+    }
+    /// The relative rotation between node and beam end, it has the sign of the moment.
+    pub fn get_rotation(&self) -> f64 {
+        self.rotation
+    }
+    /// The moment at the connection in the sign convention of the `BeamResult`.
+    pub fn get_moment(&self) -> f64 {
+        self.moment
+    }
+    /// The secant stiffness of the connection at the final state.
+    pub fn get_stiffness(&self) -> f64 {
+        self.stiffness
+    }
+}
+
+pub struct SemiRigidResultSet {
+    results: BeamResultSet,
+    connections: Vec<ConnectionResult>,
+    iterations: usize,
+    converged: bool,
+}
+
+impl SemiRigidResultSet {
+    pub fn get_results(&self) -> &BeamResultSet {
+        &self.results
+    }
+    pub fn get_connections(&self) -> &[ConnectionResult] {
+        &self.connections
+    }
+    pub fn get_iterations(&self) -> usize {
+        self.iterations
+    }
+    pub fn is_converged(&self) -> bool {
+        self.converged
+    }
+}
+
+impl System {
+    /// Solves the system with the nonlinear connections by iterating the secant stiffness of
+    /// every moment-rotation curve, using first order theory.
+    pub fn matrix_stiffness_method_semi_rigid(
+        &self,
+        loading: &SystemLoading,
+    ) -> SemiRigidResultSet {
+        let mut sys = self.clone();
+        let curves = self.get_connections();
+        let ends = self.get_connection_beams();
+
+        let mut stiffness: Vec<f64> = curves.iter().map(|c| c.get_initial_stiffness()).collect();
+        let mut iterations = 0;
+        let mut converged = false;
+        let mut res = sys.matrix_stiffness_method_first_order(loading);
+
+        while iterations < MAX_ITERATIONS {
+            iterations += 1;
+            let mut max_change = 0.0_f64;
+            for i in 0..curves.len() {
+                let m = connection_moment(&res, ends[i]);
+                let phi = m / stiffness[i];
+                let k = curves[i].get_secant_stiffness(phi);
+                max_change = max_change.max(((k - stiffness[i]) / stiffness[i]).abs());
+                stiffness[i] = k;
+            }
+            if max_change < TOLERANCE {
+                converged = true;
+                break;
+            }
+            for i in 0..curves.len() {
+                let [beam, end] = ends[i];
+                sys.get_beams_mut()[beam].set_release(end * 3 + 2, stiffness[i]);
+            }
+            res = sys.matrix_stiffness_method_first_order(loading);
+        }
+
+        let mut connections = Vec::new();
+        for i in 0..curves.len() {
+            let m = connection_moment(&res, ends[i]);
+            connections.push(ConnectionResult {
+                beam: ends[i][0],
+                end: ends[i][1],
+                rotation: m / stiffness[i],
+                moment: m,
+                stiffness: stiffness[i],
+            });
+        }
+
+        SemiRigidResultSet {
+            results: res,
+            connections,
+            iterations,
+            converged,
+        }
+    }
+}
+
+fn connection_moment(res: &BeamResultSet, beam_end: [usize; 2]) -> f64 {
+    let rsk = res.get_results()[beam_end[0]].get_rsks();
+    if beam_end[1] == 0 {
+        rsk[2]
+    } else {
+        rsk[5]
+    }
+}
@@ -1,4 +1,6 @@
 use libm::atan2;
+use libm::cos;
+use libm::sin;
 use nalgebra::OMatrix;
 use nalgebra::SMatrix;
 use nalgebra::{DVector, SVector};
@@ -10,10 +12,15 @@ type Vector7 = SVector<f64, 7>;
 /// three values resemble whether its DOF is free and whether it is associated with a feather
 /// [true, true, false]
 /// [x1  , x2  , phi3 ]
+#[derive(Clone)]
 pub struct Support {
     alpha: f64,
     is_free: [bool; 3],
     federnach: [f64; 3],
+    unilateral: Option<usize>,
+    direction: f64,
+    gap: f64,
+    separated: bool,
 }
 
 impl Support {
@@ -22,8 +29,24 @@ impl Support {
             alpha,
             is_free,
             federnach,
+            unilateral: None,
+            direction: 1.0,
+            gap: 0.0,
+            separated: false,
         }
     }
+    /// Lets the fixity or spring of the global `dof` act in one direction only.
+    ///
+    /// The support blocks displacements in `direction` (`1.0` or `-1.0`) after the `gap` is
+    /// closed, and separates from the node when it is pulled. E.g. a bearing under a beam with
+    /// downward loads: `with_unilateral(1, 1.0, 0.0)`. Only
+    /// `matrix_stiffness_method_unilateral` lets the support separate.
+    pub fn with_unilateral(mut self, dof: usize, direction: f64, gap: f64) -> Self {
+        self.unilateral = Some(dof);
+        self.direction = direction.signum();
+        self.gap = gap;
+        self
+    }
     pub fn get_alpha(&self) -> f64 {
         self.alpha
     }
@@ -33,6 +56,83 @@ impl Support {
     pub fn get_feder(&self) -> &[f64; 3] {
         &self.federnach
     }
+    pub fn get_unilateral_dof(&self) -> Option<usize> {
+        self.unilateral
+    }
+    pub fn get_unilateral_direction(&self) -> f64 {
+        self.direction
+    }
+    pub fn get_gap(&self) -> f64 {
+        self.gap
+    }
+    pub fn is_separated(&self) -> bool {
+        self.separated
+    }
+    pub(crate) fn set_separated(&mut self, separated: bool) {
+        self.separated = separated;
+    }
+    /// Whether the dof is held, it is free if the unilateral support has separated.
+    pub(crate) fn is_fixed(&self, dof: usize) -> bool {
+        !self.is_free[dof] && !(self.separated && self.unilateral == Some(dof))
+    }
+    /// Whether the spring of the dof acts, the spring is off if the unilateral support has separated.
+    pub(crate) fn has_spring(&self, dof: usize) -> bool {
+        self.is_free[dof] && !(self.separated && self.unilateral == Some(dof))
+    }
+    /// The displacement of the dof at which the support acts, the closed gap.
+    pub(crate) fn get_contact_displacement(&self, dof: usize) -> f64 {
+        if self.unilateral == Some(dof) {
+            self.direction * self.gap
+        } else {
+            0.0
+        }
+    }
+}
+
+/// A spring or link element between two points.
+///
+/// The stiffnesses [x1, x2, phi3] act in the coordinate system rotated by alpha against the global
+/// coordinate system, like the local coordinate system of a beam.
+#[derive(Clone, Copy)]
+pub struct Spring {
+    alpha: f64,
+    stiffness: [f64; 3],
+}
+
+impl Spring {
+    pub fn new(alpha: f64, stiffness: [f64; 3]) -> Self {
+        Spring { alpha, stiffness }
+    }
+    pub fn get_alpha(&self) -> f64 {
+        self.alpha
+    }
+    pub fn get_stiffness(&self) -> &[f64; 3] {
+        &self.stiffness
+    }
+}
+
+/// A linear multi-point constraint `sum(a_k * u_k) = 0` with terms (point, dof, a_k).
+///
+/// The first term is the dependent (slave) dof, which is eliminated during the assembly. It must
+/// neither be held by a support nor be the dependent dof of another constraint.
+#[derive(Clone)]
+pub struct Constraint {
+    terms: Vec<(usize, usize, f64)>,
+}
+
+impl Constraint {
+    pub fn new(terms: Vec<(usize, usize, f64)>) -> Self {
+        Constraint { terms }
+    }
+    /// The dof of the `slave` point follows the same dof of the `master` point.
+    pub fn new_equal_dof(slave: usize, master: usize, dof: usize) -> Self {
+        Constraint {
+            terms: vec![(slave, dof, 1.0), (master, dof, -1.0)],
+        }
+    }
+    pub fn get_terms(&self) -> &[(usize, usize, f64)] {
+        &self.terms
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -52,14 +152,51 @@ pub struct Crosssection {
     emodul: f64,
     area: f64,
     ftm: f64,
+    density: f64,
+    plastic_moment: Option<f64>,
+    plastic_normal_force: Option<f64>,
 }
 
 impl Crosssection {
     pub fn new(emodul: f64, area: f64, ftm: f64) -> Self {
-        Crosssection { emodul, area, ftm }
+        Crosssection {
+            emodul,
+            area,
+            ftm,
+            density: 0.0,
+            plastic_moment: None,
+            plastic_normal_force: None,
+        }
+    }
+    /// The mass per volume, the beams get the mass per length `density * area`.
+    pub fn with_density(mut self, density: f64) -> Self {
+        self.density = density;
+        self
+    }
+    /// The plastic moment M_pl, at which a plastic hinge forms.
+    pub fn with_plastic_moment(mut self, moment: f64) -> Self {
+        self.plastic_moment = Some(moment);
+        self
+    }
+    /// The plastic normal force N_pl for the linear interaction `|M| / M_pl + |N| / N_pl = 1`.
+    pub fn with_plastic_normal_force(mut self, normal_force: f64) -> Self {
+        self.plastic_normal_force = Some(normal_force);
+        self
     }
 }
 
+/// The axial behaviour of a beam, bracing and cables only act in tension, contact struts only
+/// in compression.
+///
+/// A deactivated member has no stiffness, but its line loads are still carried to the end nodes
+/// by the fixed-end forces, e.g. the self-weight of a slack cable.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum AxialBehaviour {
+    TensionAndCompression,
+    TensionOnly,
+    CompressionOnly,
+}
+
 /// x_1, x_2, phi_3 -- x_1, x_2, phi_3
 #[derive(Clone, Copy)]
 pub struct Beam {
@@ -68,6 +205,12 @@ pub struct Beam {
     dofstiffness: [f64; 6],
     start_dof_alpha: f64,
     end_dof_alpha: f64,
+    start_offset: [f64; 2],
+    end_offset: [f64; 2],
+    local_offsets: bool,
+    axial_behaviour: AxialBehaviour,
+    active: bool,
+    cable_weight: Option<f64>,
 }
 
 impl Beam {
@@ -84,8 +227,47 @@ impl Beam {
             dofstiffness: dofstiffness,
             start_dof_alpha: start,
             end_dof_alpha: end,
+            start_offset: [0.0, 0.0],
+            end_offset: [0.0, 0.0],
+            local_offsets: false,
+            axial_behaviour: AxialBehaviour::TensionAndCompression,
+            active: true,
+            cable_weight: None,
         }
     }
+    /// A cable with hinged ends, which only acts in tension.
+    ///
+    /// The `weight` per length is used for the sag and the Ernst modulus in
+    /// `matrix_stiffness_method_cable`, it is not applied as a load.
+    pub fn new_cable(cross: Crosssection, weight: f64) -> Beam {
+        let mut b = Beam::new(
+            cross,
+            [false, false, true, false, false, true],
+            [0.0; 6],
+            0.0,
+            0.0,
+        )
+        .with_axial_behaviour(AxialBehaviour::TensionOnly);
+        b.cable_weight = Some(weight);
+        b
+    }
+    /// Rigid end offsets from the nodes to the faces of the joints.
+    ///
+    /// With `local` the vectors are given along the beam axis (node to node) and the local
+    /// perpendicular direction of the line loads, otherwise in global coordinates of the points.
+    /// The beam is elastic between the faces, all results are reported at the faces.
+    pub fn with_rigid_offsets(mut self, start: [f64; 2], end: [f64; 2], local: bool) -> Beam {
+        self.start_offset = start;
+        self.end_offset = end;
+        self.local_offsets = local;
+        self
+    }
+    /// Marks the beam as tension-only or compression-only member, which is respected by
+    /// `matrix_stiffness_method_unilateral`.
+    pub fn with_axial_behaviour(mut self, behaviour: AxialBehaviour) -> Beam {
+        self.axial_behaviour = behaviour;
+        self
+    }
     pub fn get_emodul(&self) -> f64 {
         self.crosssection.emodul
     }
@@ -95,6 +277,16 @@ impl Beam {
     pub fn get_ftm(&self) -> f64 {
         self.crosssection.ftm
     }
+    /// The mass per length.
+    pub fn get_mass(&self) -> f64 {
+        self.crosssection.density * self.crosssection.area
+    }
+    pub fn get_plastic_moment(&self) -> Option<f64> {
+        self.crosssection.plastic_moment
+    }
+    pub fn get_plastic_normal_force(&self) -> Option<f64> {
+        self.crosssection.plastic_normal_force
+    }
     pub fn get_start_alpha(&self) -> f64 {
         self.start_dof_alpha
     }
@@ -107,14 +299,123 @@ impl Beam {
     pub fn get_dofstiffness(&self) -> &[f64] {
         &self.dofstiffness
     }
+    pub fn get_start_offset(&self) -> &[f64; 2] {
+        &self.start_offset
+    }
+    pub fn get_end_offset(&self) -> &[f64; 2] {
+        &self.end_offset
+    }
+    pub fn has_local_offsets(&self) -> bool {
+        self.local_offsets
+    }
+    pub fn get_axial_behaviour(&self) -> AxialBehaviour {
+        self.axial_behaviour
+    }
+    /// Whether the beam takes part in the load transfer, inactive beams are skipped by the solvers.
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+    pub fn is_cable(&self) -> bool {
+        self.cable_weight.is_some()
+    }
+    pub fn get_cable_weight(&self) -> f64 {
+        self.cable_weight.unwrap_or(0.0)
+    }
+    pub(crate) fn set_emodul(&mut self, emodul: f64) {
+        self.crosssection.emodul = emodul;
+    }
+    pub(crate) fn set_active(&mut self, active: bool) {
+        self.active = active;
+    }
+    pub(crate) fn set_release(&mut self, dof: usize, stiffness: f64) {
+        self.dof[dof] = true;
+        self.dofstiffness[dof] = stiffness;
+    }
+}
+
+/// A nonlinear moment-rotation curve of a beam connection.
+///
+/// The curve is given by points `[phi, M]` with increasing rotation, starting implicitly at the
+/// origin. It is symmetric for negative rotations and extended with the slope of the last segment.
+#[derive(Clone)]
+pub struct MomentRotationCurve {
+    points: Vec<[f64; 2]>,
+}
+
+impl MomentRotationCurve {
+    /// A curve through the points, a leading point at the origin is dropped.
+    ///
+    /// Panics if the rotations are not positive and strictly increasing.
+    pub fn new_multilinear(mut points: Vec<[f64; 2]>) -> Self {
+        // Der Ursprung ist bereits enthalten
+        if points.first() == Some(&[0.0, 0.0]) {
+            points.remove(0);
+        }
+        if points.is_empty() {
+            panic!("Die Momenten-Rotations-Kurve braucht einen Punkt außerhalb des Ursprungs.");
+        }
+        let mut last = 0.0;
+        for p in &points {
+            if p[0].is_nan() || p[0] <= last {
+                panic!("Die Verdrehungen der Momenten-Rotations-Kurve müssen positiv und streng monoton steigend sein.");
+            }
+            last = p[0];
+        }
+        MomentRotationCurve { points }
+    }
+    /// A bilinear curve with the initial stiffness up to the yield moment and the hardening
+    /// stiffness afterwards, a hardening of `0.0` results in a plateau.
+    pub fn new_bilinear(initial_stiffness: f64, yield_moment: f64, hardening: f64) -> Self {
+        let phi_y = yield_moment / initial_stiffness;
+        MomentRotationCurve {
+            points: vec![
+                [phi_y, yield_moment],
+                [phi_y + 1.0, yield_moment + hardening],
+            ],
+        }
+    }
+    pub fn get_points(&self) -> &[[f64; 2]] {
+        &self.points
+    }
+    pub fn get_initial_stiffness(&self) -> f64 {
+        self.points[0][1] / self.points[0][0]
+    }
+    pub fn get_moment_at(&self, phi: f64) -> f64 {
+        let a = phi.abs();
+        let mut last = [0.0, 0.0];
+        for i in 0..self.points.len() {
+            let p = self.points[i];
+            if a <= p[0] || i == self.points.len() - 1 {
+                let m = last[1] + (p[1] - last[1]) / (p[0] - last[0]) * (a - last[0]);
+                return m.copysign(phi);
+            }
+            last = p;
+        }
+        return 0.0;
+    }
+    /// The secant stiffness `M(phi) / phi`, the initial stiffness for `phi = 0`.
+    pub fn get_secant_stiffness(&self, phi: f64) -> f64 {
+        if phi.abs() <= self.points[0][0] {
+            return self.get_initial_stiffness();
+        }
+        self.get_moment_at(phi) / phi
+    }
 }
 
+#[derive(Clone)]
 pub struct System {
     points: Vec<Point>,
     beam_points: Vec<[usize; 2]>,
     beams: Vec<Beam>,
     support_points: Vec<usize>,
     supports: Vec<Support>,
+    connection_beams: Vec<[usize; 2]>,
+    connections: Vec<MomentRotationCurve>,
+    spring_points: Vec<[usize; 2]>,
+    springs: Vec<Spring>,
+    constraints: Vec<Constraint>,
+    mass_points: Vec<usize>,
+    masses: Vec<[f64; 3]>,
 }
 
 impl System {
@@ -131,7 +432,64 @@ impl System {
             beams,
             support_points,
             supports,
+            connection_beams: Vec::new(),
+            connections: Vec::new(),
+            spring_points: Vec::new(),
+            springs: Vec::new(),
+            constraints: Vec::new(),
+            mass_points: Vec::new(),
+            masses: Vec::new(),
+        }
+    }
+    pub fn add_constraint(&mut self, constraint: Constraint) {
+        self.constraints.push(constraint);
+    }
+    /// Connects the `slave` point with a rigid arm to the `master` point, all three dofs of the
+    /// slave point follow the rigid body motion of the master point.
+    pub fn add_rigid_link(&mut self, master: usize, slave: usize) {
+        let m = self.points[master];
+        let s = self.points[slave];
+        // Verschiebungen nach unten positiv, Verdrehung im Uhrzeigersinn
+        self.add_constraint(Constraint::new(vec![
+            (slave, 0, 1.0),
+            (master, 0, -1.0),
+            (master, 2, -(s.y - m.y)),
+        ]));
+        self.add_constraint(Constraint::new(vec![
+            (slave, 1, 1.0),
+            (master, 1, -1.0),
+            (master, 2, -(s.x - m.x)),
+        ]));
+        self.add_constraint(Constraint::new_equal_dof(slave, master, 2));
+    }
+    /// Adds a spring between the points `from` and `to`. They have to be distinct points, but
+    /// may lie at the same location, e.g. for a rotational spring in a joint.
+    pub fn add_spring(&mut self, from: usize, to: usize, spring: Spring) {
+        if from == to {
+            panic!("Die Feder verbindet den Punkt {} mit sich selbst.", from);
         }
+        self.spring_points.push([from, to]);
+        self.springs.push(spring);
+    }
+    /// Adds a point mass with the masses [x1, x2] and the rotational inertia phi3.
+    pub fn add_point_mass(&mut self, point: usize, mass: [f64; 3]) {
+        self.mass_points.push(point);
+        self.masses.push(mass);
+    }
+    /// Adds a semi-rigid connection at the start (`end = 0`) or the end (`end = 1`) of a beam.
+    ///
+    /// The rotational dof of this beam end is released with the initial stiffness of the curve,
+    /// the linear solvers use this stiffness, `matrix_stiffness_method_semi_rigid` iterates the
+    /// curve.
+    pub fn add_semi_rigid_connection(
+        &mut self,
+        beamindex: usize,
+        end: usize,
+        curve: MomentRotationCurve,
+    ) {
+        self.beams[beamindex].set_release(end * 3 + 2, curve.get_initial_stiffness());
+        self.connection_beams.push([beamindex, end]);
+        self.connections.push(curve);
     }
     pub fn get_points(&self) -> &[Point] {
         return &self.points;
@@ -148,32 +506,189 @@ impl System {
     pub fn get_supports(&self) -> &[Support] {
         return &self.supports;
     }
+    pub fn get_connection_beams(&self) -> &[[usize; 2]] {
+        return &self.connection_beams;
+    }
+    pub fn get_connections(&self) -> &[MomentRotationCurve] {
+        return &self.connections;
+    }
+    pub fn get_mass_points(&self) -> &[usize] {
+        return &self.mass_points;
+    }
+    pub fn get_masses(&self) -> &[[f64; 3]] {
+        return &self.masses;
+    }
+    pub fn get_spring_points(&self) -> &[[usize; 2]] {
+        return &self.spring_points;
+    }
+    pub fn get_springs(&self) -> &[Spring] {
+        return &self.springs;
+    }
+    pub fn get_constraints(&self) -> &[Constraint] {
+        return &self.constraints;
+    }
+    pub(crate) fn get_beams_mut(&mut self) -> &mut [Beam] {
+        return &mut self.beams;
+    }
+    pub(crate) fn get_supports_mut(&mut self) -> &mut [Support] {
+        return &mut self.supports;
+    }
+    /// The elastic length of the beam, measured between the faces of the joints.
     pub fn get_beam_lenght(&self, beamindex: usize) -> f64 {
-        let p = self.beam_points[beamindex];
-        let point_one = &self.points[p[0]];
-        let point_two = &self.points[p[1]];
+        let point_one = self.get_beam_start_face(beamindex);
+        let point_two = self.get_beam_end_face(beamindex);
         return ((point_one.x - point_two.x).powi(2) + (point_one.y - point_two.y).powi(2)).sqrt();
     }
+    /// The angle of the elastic part of the beam, measured between the faces of the joints.
     pub fn get_beam_alpha(&self, beamindex: usize) -> f64 {
+        let point_one = self.get_beam_start_face(beamindex);
+        let point_two = self.get_beam_end_face(beamindex);
+        let r = atan2(point_one.y - point_two.y, point_one.x - point_two.x);
+        return consts::PI + r;
+    }
+    /// The angle of the line from node to node, ignoring the rigid end offsets.
+    pub fn get_beam_node_alpha(&self, beamindex: usize) -> f64 {
         let p = self.beam_points[beamindex];
         let point_one = &self.points[p[0]];
         let point_two = &self.points[p[1]];
         let r = atan2(point_one.y - point_two.y, point_one.x - point_two.x);
         return consts::PI + r;
     }
+    /// The rigid end offsets of the beam in global coordinates of the points.
+    pub fn get_beam_global_offsets(&self, beamindex: usize) -> [[f64; 2]; 2] {
+        let b = &self.beams[beamindex];
+        let s = *b.get_start_offset();
+        let e = *b.get_end_offset();
+        if !b.has_local_offsets() {
+            return [s, e];
+        }
+        let alpha = self.get_beam_node_alpha(beamindex);
+        let (c, si) = (cos(alpha), sin(alpha));
+        // lokale x-Achse (c, s), lokale Querrichtung (s, -c) wie bei den Streckenlasten
+        return [
+            [s[0] * c + s[1] * si, s[0] * si - s[1] * c],
+            [e[0] * c + e[1] * si, e[0] * si - e[1] * c],
+        ];
+    }
+    pub fn get_beam_start_face(&self, beamindex: usize) -> Point {
+        let p = self.points[self.beam_points[beamindex][0]];
+        let o = self.get_beam_global_offsets(beamindex)[0];
+        Point::new(p.x + o[0], p.y + o[1])
+    }
+    pub fn get_beam_end_face(&self, beamindex: usize) -> Point {
+        let p = self.points[self.beam_points[beamindex][1]];
+        let o = self.get_beam_global_offsets(beamindex)[1];
+        Point::new(p.x + o[0], p.y + o[1])
+    }
     pub fn get_beam_from_point(&self, beamindex: usize) -> usize {
         return self.beam_points[beamindex][0];
     }
     pub fn get_beam_to_point(&self, beamindex: usize) -> usize {
         return self.beam_points[beamindex][1];
     }
+
+    /// Panics for releases other than moment releases without spring, which the classical methods
+    /// do not support.
+    pub(crate) fn check_moment_releases(&self) {
+        for b in self.get_beams() {
+            for d in 0..6 {
+                if b.get_dofs()[d] && (d % 3 != 2 || b.get_dofstiffness()[d] != 0.0) {
+                    panic!("Nur Momentengelenke ohne Feder werden unterstützt.");
+                }
+            }
+        }
+    }
+    /// Panics for springs of rotated supports, which the classical methods cannot rotate like the
+    /// stiffness method.
+    pub(crate) fn check_unrotated_support_springs(&self) {
+        for sup in self.get_supports() {
+            let spring = (0..3).any(|d| sup.has_spring(d) && sup.get_feder()[d] != 0.0);
+            if spring && sup.get_alpha() != 0.0 {
+                panic!("Federn gedrehter Lager werden nicht unterstützt.");
+            }
+        }
+    }
+
+    /// A copy of the system, whose beams are split at the given positions measured from the
+    /// start face. The new points are appended, so the indices of the old points stay valid.
+    ///
+    /// Returns for every old beam its parts in order as `(beam, start position)`. Releases and
+    /// offsets remain at the outer ends of the first and the last part.
+    pub(crate) fn subdivide(&self, positions: &[Vec<f64>]) -> (System, Vec<Vec<(usize, f64)>>) {
+        let mut sys = self.clone();
+        sys.beam_points = Vec::new();
+        sys.beams = Vec::new();
+        let mut parts = Vec::new();
+        for b in 0..self.beams.len() {
+            let beam = self.beams[b];
+            let l = self.get_beam_lenght(b);
+            let tol = 1e-9 * l;
+            let mut xs: Vec<f64> = positions
+                .get(b)
+                .cloned()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|&x| x > tol && x < l - tol)
+                .collect();
+            xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+            xs.dedup_by(|a, b| (*a - *b).abs() < tol);
+
+            let start = self.get_beam_start_face(b);
+            let end = self.get_beam_end_face(b);
+            let offsets = self.get_beam_global_offsets(b);
+            let mut nodes = vec![self.beam_points[b][0]];
+            for &x in &xs {
+                sys.points.push(Point::new(
+                    start.x + (end.x - start.x) * x / l,
+                    start.y + (end.y - start.y) * x / l,
+                ));
+                nodes.push(sys.points.len() - 1);
+            }
+            nodes.push(self.beam_points[b][1]);
+            xs.insert(0, 0.0);
+
+            let mut p = Vec::new();
+            let n = nodes.len() - 1;
+            for k in 0..n {
+                let mut part = beam;
+                part.local_offsets = false;
+                part.start_offset = if k == 0 { offsets[0] } else { [0.0, 0.0] };
+                part.end_offset = if k == n - 1 { offsets[1] } else { [0.0, 0.0] };
+                // Gelenke nur an den äußeren Enden
+                if k > 0 {
+                    for d in 0..3 {
+                        part.dof[d] = false;
+                        part.dofstiffness[d] = 0.0;
+                    }
+                }
+                if k < n - 1 {
+                    for d in 3..6 {
+                        part.dof[d] = false;
+                        part.dofstiffness[d] = 0.0;
+                    }
+                }
+                sys.beam_points.push([nodes[k], nodes[k + 1]]);
+                sys.beams.push(part);
+                p.push((sys.beams.len() - 1, xs[k]));
+            }
+            parts.push(p);
+        }
+        for c in sys.connection_beams.iter_mut() {
+            let p = &parts[c[0]];
+            c[0] = if c[1] == 0 { p[0].0 } else { p[p.len() - 1].0 };
+        }
+        return (sys, parts);
+    }
 }
 
+#[derive(Clone)]
 pub struct SystemLoading {
     loaded_points: Vec<usize>,
     staticloads: Vec<StaticLoad>,
     loaded_beams: Vec<usize>,
     lineloads: Vec<StaticLinearLineload>,
+    settled_supports: Vec<usize>,
+    settlements: Vec<[f64; 3]>,
 }
 
 impl SystemLoading {
@@ -188,8 +703,21 @@ impl SystemLoading {
             staticloads,
             loaded_beams,
             lineloads,
+            settled_supports: Vec::new(),
+            settlements: Vec::new(),
         };
     }
+    /// Prescribed displacements [x1, x2, phi3] of the supports with the given indices, e.g.
+    /// settlements. They act on the fixed dofs and on the base of the support springs.
+    pub fn with_support_displacements(
+        mut self,
+        supports: Vec<usize>,
+        displacements: Vec<[f64; 3]>,
+    ) -> Self {
+        self.settled_supports = supports;
+        self.settlements = displacements;
+        self
+    }
 
     pub fn get_static_loads(&self) -> &[StaticLoad] {
         &self.staticloads
@@ -197,6 +725,18 @@ impl SystemLoading {
     pub fn get_static_load_points(&self) -> &[usize] {
         &self.loaded_points
     }
+    pub fn get_loaded_beams(&self) -> &[usize] {
+        &self.loaded_beams
+    }
+    pub fn get_lineloads(&self) -> &[StaticLinearLineload] {
+        &self.lineloads
+    }
+    pub fn get_settled_supports(&self) -> &[usize] {
+        &self.settled_supports
+    }
+    pub fn get_settlements(&self) -> &[[f64; 3]] {
+        &self.settlements
+    }
 
     pub fn get_total_lineload_for_beam(&self, beamindex: usize) -> StaticLinearLineload {
         let mut res = StaticLinearLineload::new_constant_load(0.0);
@@ -207,8 +747,82 @@ impl SystemLoading {
         }
         return res;
     }
+
+    /// The loading of the system subdivided by `System::subdivide`, the line loads are
+    /// distributed on the parts of their beams.
+    pub(crate) fn subdivide(&self, lengths: &[f64], parts: &[Vec<(usize, f64)>]) -> SystemLoading {
+        let mut res = self.clone();
+        res.loaded_beams = Vec::new();
+        res.lineloads = Vec::new();
+        for i in 0..self.loaded_beams.len() {
+            let b = self.loaded_beams[i];
+            let l = lengths[b];
+            let q = &self.lineloads[i];
+            let q_at = |x: f64| {
+                q.get_from_perpendicular_load()
+                    + (q.get_to_perpendicular_load() - q.get_from_perpendicular_load()) * x / l
+            };
+            let p = &parts[b];
+            for k in 0..p.len() {
+                let x1 = if k + 1 < p.len() { p[k + 1].1 } else { l };
+                res.loaded_beams.push(p[k].0);
+                res.lineloads.push(StaticLinearLineload::new_linear_load(
+                    q_at(p[k].1),
+                    q_at(x1),
+                ));
+            }
+        }
+        return res;
+    }
+
+    pub fn get_total_support_displacement(&self, supportindex: usize) -> [f64; 3] {
+        let mut res = [0.0; 3];
+        for i in 0..self.settled_supports.len() {
+            if self.settled_supports[i] == supportindex {
+                for j in 0..3 {
+                    res[j] += self.settlements[i][j];
+                }
+            }
+        }
+        return res;
+    }
+
+    /// The loading with all loads and prescribed displacements multiplied by `factor`.
+    pub fn scale(&self, factor: f64) -> SystemLoading {
+        let mut res = self.clone();
+        for l in res.staticloads.iter_mut() {
+            for j in 0..3 {
+                l.loading[j] *= factor;
+            }
+        }
+        for l in res.lineloads.iter_mut() {
+            for j in 0..4 {
+                l.loading[j] *= factor;
+            }
+        }
+        for d in res.settlements.iter_mut() {
+            for j in 0..3 {
+                d[j] *= factor;
+            }
+        }
+        return res;
+    }
+
+    /// The superposition of both loadings.
+    pub fn add(&self, other: &SystemLoading) -> SystemLoading {
+        let mut res = self.clone();
+        res.loaded_points.extend_from_slice(&other.loaded_points);
+        res.staticloads.extend_from_slice(&other.staticloads);
+        res.loaded_beams.extend_from_slice(&other.loaded_beams);
+        res.lineloads.extend_from_slice(&other.lineloads);
+        res.settled_supports
+            .extend_from_slice(&other.settled_supports);
+        res.settlements.extend_from_slice(&other.settlements);
+        return res;
+    }
 }
 
+#[derive(Clone, Copy)]
 pub struct StaticLoad {
     loading: [f64; 3], // x1 x2 phi3
 }
@@ -0,0 +1,243 @@
+use crate::stiffness::direct_stiffness::BeamResultSet;
+use crate::stiffness::modal::MassMatrix;
+use crate::stiffness::system::*;
+use nalgebra::DVector;
+
+type VectorD = DVector<f64>;
+
+/// Rayleigh damping `C = a M + b K`.
+#[derive(Clone, Copy, Debug)]
+pub struct RayleighDamping {
+    mass_factor: f64,
+    stiffness_factor: f64,
+}
+
+impl RayleighDamping {
+    pub fn new(mass_factor: f64, stiffness_factor: f64) -> Self {
+        RayleighDamping {
+            mass_factor,
+            stiffness_factor,
+        }
+    }
+    /// The factors, which result in the damping ratio `zeta` at the two angular frequencies.
+    pub fn from_damping_ratio(zeta: f64, omega_1: f64, omega_2: f64) -> Self {
+        RayleighDamping::new(
+            2.0 * zeta * omega_1 * omega_2 / (omega_1 + omega_2),
+            2.0 * zeta / (omega_1 + omega_2),
+        )
+    }
+    pub fn get_mass_factor(&self) -> f64 {
+        self.mass_factor
+    }
+    pub fn get_stiffness_factor(&self) -> f64 {
+        self.stiffness_factor
+    }
+}
+
+/// The time integration scheme.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TimeIntegration {
+    /// The Newmark method with the parameters beta and gamma, e.g. `beta = 1/4, gamma = 1/2`
+    /// (average acceleration, unconditionally stable) or `beta = 1/6, gamma = 1/2` (linear
+    /// acceleration).
+    Newmark { beta: f64, gamma: f64 },
+    /// The HHT-alpha method with `-1/3 <= alpha <= 0`, which damps the high frequencies
+    /// numerically. `alpha = 0` is the average acceleration method.
+    HhtAlpha(f64),
+}
+
+impl TimeIntegration {
+    /// [alpha, beta, gamma] of the HHT formulation.
+    fn get_parameters(&self) -> [f64; 3] {
+        match *self {
+            TimeIntegration::Newmark { beta, gamma } => [0.0, beta, gamma],
+            TimeIntegration::HhtAlpha(alpha) => [
+                alpha,
+                0.25 * (1.0 - alpha) * (1.0 - alpha),
+                0.5 * (1.0 - 2.0 * alpha),
+            ],
+        }
+    }
+}
+
+/// A loading multiplied with a time dependent factor.
+#[derive(Clone)]
+pub struct LoadHistory {
+    loading: SystemLoading,
+    series: Vec<[f64; 2]>,
+}
+
+impl LoadHistory {
+    /// The factor is given by points `[t, factor]` with increasing time, linear in between and
+    /// zero outside of the series.
+    pub fn new(loading: SystemLoading, series: Vec<[f64; 2]>) -> Self {
+        LoadHistory { loading, series }
+    }
+    pub fn get_loading(&self) -> &SystemLoading {
+        &self.loading
+    }
+    pub fn get_series(&self) -> &[[f64; 2]] {
+        &self.series
+    }
+    pub fn get_factor_at(&self, t: f64) -> f64 {
+        let s = &self.series;
+        if s.is_empty() || t < s[0][0] || t > s[s.len() - 1][0] {
+            return 0.0;
+        }
+        for i in 0..s.len() - 1 {
+            if t <= s[i + 1][0] {
+                let dt = s[i + 1][0] - s[i][0];
+                if dt <= 0.0 {
+                    return s[i + 1][1];
+                }
+                let r = (t - s[i][0]) / dt;
+                return s[i][1] * (1.0 - r) + s[i + 1][1] * r;
+            }
+        }
+        return s[s.len() - 1][1];
+    }
+}
+
+pub struct TimeHistoryResultSet {
+    times: Vec<f64>,
+    displacements: Vec<Vec<[f64; 3]>>,
+    velocities: Vec<Vec<[f64; 3]>>,
+    accelerations: Vec<Vec<[f64; 3]>>,
+    results: Vec<BeamResultSet>,
+}
+
+impl TimeHistoryResultSet {
+    pub fn get_times(&self) -> &[f64] {
+        &self.times
+    }
+    /// The displacements x_1, x_2, phi_3 of every point at every time step.
+    pub fn get_displacements(&self) -> &[Vec<[f64; 3]>] {
+        &self.displacements
+    }
+    pub fn get_velocities(&self) -> &[Vec<[f64; 3]>] {
+        &self.velocities
+    }
+    pub fn get_accelerations(&self) -> &[Vec<[f64; 3]>] {
+        &self.accelerations
+    }
+    /// The beam results at every time step.
+    pub fn get_results(&self) -> &[BeamResultSet] {
+        &self.results
+    }
+    /// The history of the value [N, Q, M, u, w, phi] with the index `component` at the position
+    /// of the beam.
+    pub fn get_beam_history(&self, beamindex: usize, x: f64, component: usize) -> Vec<f64> {
+        self.results
+            .iter()
+            .map(|r| r.get_results()[beamindex].get_internals_at(x)[component])
+            .collect()
+    }
+    /// The history of the displacement of the point in the direction `dof`.
+    pub fn get_point_history(&self, point: usize, dof: usize) -> Vec<f64> {
+        self.displacements.iter().map(|u| u[point][dof]).collect()
+    }
+}
+
+impl System {
+    /// Integrates `M a + C v + K u = f(t)` with first order theory, starting at rest at `t = 0`.
+    ///
+    /// The internal forces of the beams are recovered from the displacements of every time step
+    /// with the current loading. The effective stiffness matrix is factorised once. With a
+    /// singular mass matrix the initial acceleration is taken from its pseudo inverse. Support
+    /// displacements of the loadings are not considered.
+    pub fn time_history(
+        &self,
+        histories: &[LoadHistory],
+        mass: MassMatrix,
+        damping: RayleighDamping,
+        integration: TimeIntegration,
+        dt: f64,
+        steps: usize,
+    ) -> TimeHistoryResultSet {
+        let empty = SystemLoading::new(Vec::new(), Vec::new(), Vec::new(), Vec::new());
+        let (k, m, t) = self.reduced_dynamic_system(mass);
+        let c = damping.get_mass_factor() * &m + damping.get_stiffness_factor() * &k;
+        let (_, u0) = self.dof_transformation(&empty);
+        let steif = self.global_stiffness_first_order();
+
+        // Konstanter Anteil und Anteile der einzelnen Lastfälle
+        let base = self.load_vector_first_order(&empty);
+        let parts: Vec<VectorD> = histories
+            .iter()
+            .map(|h| t.transpose() * (self.load_vector_first_order(h.get_loading()) - &base))
+            .collect();
+        let base = t.transpose() * (&base - &steif * &u0);
+        let force = |time: f64| {
+            let mut f = base.clone();
+            for (h, p) in histories.iter().zip(parts.iter()) {
+                f += h.get_factor_at(time) * p;
+            }
+            f
+        };
+        let loading_at = |time: f64| {
+            let mut l = empty.clone();
+            for h in histories {
+                l = l.add(&h.get_loading().scale(h.get_factor_at(time)));
+            }
+            l
+        };
+
+        let [alpha, beta, gamma] = integration.get_parameters();
+        let c0 = 1.0 / (beta * dt * dt);
+        let k_eff = c0 * &m + (1.0 + alpha) * gamma * dt * c0 * &c + (1.0 + alpha) * &k;
+        let k_eff = match k_eff.cholesky() {
+            Some(t) => t,
+            None => panic!("Matrix nicht positiv definit."),
+        };
+
+        let n = k.nrows();
+        let mut u = VectorD::zeros(n);
+        let mut v = VectorD::zeros(n);
+        let mut f = force(0.0);
+        // Die Anfangsbeschleunigung, auch bei Freiheitsgraden ohne Masse
+        let eps = 1e-12 * m.amax();
+        let mut a = match m.clone().pseudo_inverse(eps) {
+            Ok(t) => t * &f,
+            Err(_) => VectorD::zeros(n),
+        };
+
+        let mut res = TimeHistoryResultSet {
+            times: Vec::new(),
+            displacements: Vec::new(),
+            velocities: Vec::new(),
+            accelerations: Vec::new(),
+            results: Vec::new(),
+        };
+        let to_points = |x: &VectorD| -> Vec<[f64; 3]> {
+            (0..x.len() / 3)
+                .map(|p| [x[p * 3], x[p * 3 + 1], x[p * 3 + 2]])
+                .collect()
+        };
+        for step in 0..=steps {
+            let time = step as f64 * dt;
+            if step > 0 {
+                let f1 = force(time);
+                // a_1 = c0 u_1 + a_t, v_1 = gamma dt c0 u_1 + v_t
+                let a_t = -c0 * (&u + dt * &v) - (0.5 / beta - 1.0) * &a;
+                let v_t = &v + dt * (1.0 - gamma) * &a + gamma * dt * &a_t;
+                let rhs =
+                    (1.0 + alpha) * &f1 - alpha * &f - &m * &a_t - (1.0 + alpha) * (&c * &v_t)
+                        + alpha * (&c * &v)
+                        + alpha * (&k * &u);
+                let u1 = k_eff.solve(&rhs);
+                a = c0 * &u1 + a_t;
+                v = gamma * dt * c0 * &u1 + v_t;
+                u = u1;
+                f = f1;
+            }
+            let ug = &t * &u + &u0;
+            res.times.push(time);
+            res.displacements.push(to_points(&ug));
+            res.velocities.push(to_points(&(&t * &v)));
+            res.accelerations.push(to_points(&(&t * &a)));
+            res.results
+                .push(self.beam_results_first_order(&loading_at(time), &ug));
+        }
+        return res;
+    }
+}
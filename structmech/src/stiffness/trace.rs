@@ -0,0 +1,442 @@
+use crate::stiffness::direct_stiffness::{transmatrix6x6, BeamResultSet};
+use crate::stiffness::system::*;
+use nalgebra::DVector;
+use nalgebra::Dynamic;
+use nalgebra::OMatrix;
+use nalgebra::SMatrix;
+use nalgebra::SVector;
+
+type Matrix6x6 = SMatrix<f64, 6, 6>;
+type Vector6 = SVector<f64, 6>;
+type MatrixDxD = OMatrix<f64, Dynamic, Dynamic>;
+type VectorD = DVector<f64>;
+
+/// The recorded steps of one active beam.
+pub struct BeamTrace {
+    beam: usize,
+    length: f64,
+    alpha: f64,
+    local_stiffness: Matrix6x6,
+    local_load: Vector6,
+    transformation: Matrix6x6,
+    offsets: Option<Matrix6x6>,
+    global_stiffness: Matrix6x6,
+    global_load: Vector6,
+    local_displacements: Vector6,
+    end_forces: Vector6,
+}
+
+impl BeamTrace {
+    pub fn get_beam(&self) -> usize {
+        self.beam
+    }
+    pub fn get_length(&self) -> f64 {
+        self.length
+    }
+    pub fn get_alpha(&self) -> f64 {
+        self.alpha
+    }
+    /// The local stiffness matrix k after the releases of the beam have been condensed.
+    pub fn get_local_stiffness(&self) -> &Matrix6x6 {
+        &self.local_stiffness
+    }
+    /// The local fixed end forces of the line loads.
+    pub fn get_local_load(&self) -> &Vector6 {
+        &self.local_load
+    }
+    /// The transformation matrix T from local to global coordinates, see `transmatrix6x6`.
+    pub fn get_transformation(&self) -> &Matrix6x6 {
+        &self.transformation
+    }
+    /// The transformation E from the nodes to the faces of the rigid offsets, if there are any.
+    pub fn get_offsets(&self) -> Option<&Matrix6x6> {
+        self.offsets.as_ref()
+    }
+    /// The global stiffness matrix K = E^T T k T^T E of the beam.
+    pub fn get_global_stiffness(&self) -> &Matrix6x6 {
+        &self.global_stiffness
+    }
+    /// The global fixed end forces E^T T f0.
+    pub fn get_global_load(&self) -> &Vector6 {
+        &self.global_load
+    }
+    /// The local displacements v = T^T E u from the solution.
+    pub fn get_local_displacements(&self) -> &Vector6 {
+        &self.local_displacements
+    }
+    /// The local end forces s = k v + f0 acting on the beam.
+    pub fn get_end_forces(&self) -> &Vector6 {
+        &self.end_forces
+    }
+}
+
+/// The intermediate results of the direct stiffness method after first order theory.
+pub struct StiffnessTrace {
+    beams: Vec<BeamTrace>,
+    stiffness: MatrixDxD,
+    load: VectorD,
+    fixed: Vec<usize>,
+    transformation: MatrixDxD,
+    prescribed: VectorD,
+    reduced_stiffness: MatrixDxD,
+    reduced_load: VectorD,
+    reduced_displacements: VectorD,
+    displacements: VectorD,
+    results: BeamResultSet,
+}
+
+impl StiffnessTrace {
+    pub fn get_beams(&self) -> &[BeamTrace] {
+        &self.beams
+    }
+    /// The assembled global stiffness matrix with springs and support springs.
+    pub fn get_stiffness(&self) -> &MatrixDxD {
+        &self.stiffness
+    }
+    /// The assembled global load vector of the point loads and the fixed end forces.
+    pub fn get_load(&self) -> &VectorD {
+        &self.load
+    }
+    /// The global dofs, which are held by the supports.
+    pub fn get_fixed_dofs(&self) -> &[usize] {
+        &self.fixed
+    }
+    /// The transformation u = T u_red + u_0 on the independent dofs.
+    pub fn get_transformation(&self) -> &MatrixDxD {
+        &self.transformation
+    }
+    /// The prescribed displacements u_0 of the held dofs.
+    pub fn get_prescribed(&self) -> &VectorD {
+        &self.prescribed
+    }
+    /// The reduced stiffness matrix T^T K T.
+    pub fn get_reduced_stiffness(&self) -> &MatrixDxD {
+        &self.reduced_stiffness
+    }
+    /// The reduced load vector T^T (P - K u_0).
+    pub fn get_reduced_load(&self) -> &VectorD {
+        &self.reduced_load
+    }
+    pub fn get_reduced_displacements(&self) -> &VectorD {
+        &self.reduced_displacements
+    }
+    /// The global displacements of all points.
+    pub fn get_displacements(&self) -> &VectorD {
+        &self.displacements
+    }
+    /// The results, equal to `matrix_stiffness_method_first_order`.
+    pub fn get_results(&self) -> &BeamResultSet {
+        &self.results
+    }
+
+    /// The steps as a Markdown document with the formulas in `$$` blocks.
+    pub fn to_markdown(&self) -> String {
+        self.render(false)
+    }
+
+    /// The steps as a LaTeX document, which needs `amsmath`.
+    pub fn to_latex(&self) -> String {
+        let mut s = String::from(
+            "\\documentclass{article}\n\\usepackage{amsmath}\n\\setcounter{MaxMatrixCols}{99}\n\\begin{document}\n\n",
+        );
+        s.push_str(&self.render(true));
+        s.push_str("\\end{document}\n");
+        return s;
+    }
+
+    fn render(&self, latex: bool) -> String {
+        let mut s = String::new();
+        let heading = |s: &mut String, title: &str| {
+            if latex {
+                s.push_str(&format!("\\section*{{{}}}\n\n", title));
+            } else {
+                s.push_str(&format!("## {}\n\n", title));
+            }
+        };
+        let formula = |s: &mut String, f: &str| {
+            if latex {
+                s.push_str(&format!("\\[\n{}\n\\]\n\n", f));
+            } else {
+                s.push_str(&format!("$$\n{}\n$$\n\n", f));
+            }
+        };
+
+        heading(&mut s, "1. Local stiffness matrices");
+        for b in &self.beams {
+            s.push_str(&format!(
+                "Beam {} with the length {} and the angle {} rad.\n\n",
+                b.beam,
+                number(b.length),
+                number(b.alpha)
+            ));
+            let k = &b.local_stiffness;
+            let f = &b.local_load;
+            formula(
+                &mut s,
+                &format!(
+                    "k_{{{0}}} = {1}, \\quad f^0_{{{0}}} = {2}",
+                    b.beam,
+                    matrix(6, 6, |i, j| k[(i, j)]),
+                    matrix(6, 1, |i, _| f[i])
+                ),
+            );
+        }
+
+        heading(&mut s, "2. Transformation to global coordinates");
+        for b in &self.beams {
+            let t = &b.transformation;
+            formula(
+                &mut s,
+                &format!("T_{{{}}} = {}", b.beam, matrix(6, 6, |i, j| t[(i, j)])),
+            );
+            let global = match &b.offsets {
+                Some(e) => {
+                    formula(
+                        &mut s,
+                        &format!("E_{{{}}} = {}", b.beam, matrix(6, 6, |i, j| e[(i, j)])),
+                    );
+                    format!(
+                        "E_{{{0}}}^T T_{{{0}}} k_{{{0}}} T_{{{0}}}^T E_{{{0}}}",
+                        b.beam
+                    )
+                }
+                None => format!("T_{{{0}}} k_{{{0}}} T_{{{0}}}^T", b.beam),
+            };
+            let k = &b.global_stiffness;
+            let f = &b.global_load;
+            formula(
+                &mut s,
+                &format!(
+                    "K_{{{0}}} = {1} = {2}, \\quad F^0_{{{0}}} = {3}",
+                    b.beam,
+                    global,
+                    matrix(6, 6, |i, j| k[(i, j)]),
+                    matrix(6, 1, |i, _| f[i])
+                ),
+            );
+        }
+
+        heading(&mut s, "3. Assembly");
+        s.push_str(
+            "The beam matrices are added at the dofs of their points, the fixed end forces are \
+             subtracted from the point loads.\n\n",
+        );
+        let n = self.load.len();
+        formula(
+            &mut s,
+            &format!(
+                "K = {}, \\quad P = {}",
+                matrix(n, n, |i, j| self.stiffness[(i, j)]),
+                matrix(n, 1, |i, _| self.load[i])
+            ),
+        );
+
+        heading(&mut s, "4. Boundary conditions");
+        let dofs: Vec<String> = self
+            .fixed
+            .iter()
+            .map(|&d| {
+                format!(
+                    "{} of point {} = {}",
+                    ["x1", "x2", "phi3"][d % 3],
+                    d / 3,
+                    number(self.prescribed[d])
+                )
+            })
+            .collect();
+        s.push_str(&format!(
+            "The supports hold the dofs: {}. Their rows and columns are eliminated.\n\n",
+            dofs.join(", ")
+        ));
+        let m = self.reduced_load.len();
+        if self.transformation.ncols() + self.fixed.len() < n {
+            s.push_str("The dependent dofs of the constraints are expressed by the others.\n\n");
+            formula(
+                &mut s,
+                &format!(
+                    "u = T u_{{red}} + u_0, \\quad T = {}, \\quad u_0 = {}",
+                    matrix(n, m, |i, j| self.transformation[(i, j)]),
+                    matrix(n, 1, |i, _| self.prescribed[i])
+                ),
+            );
+        }
+        formula(
+            &mut s,
+            &format!(
+                "K_{{red}} = {}, \\quad P_{{red}} = {}",
+                matrix(m, m, |i, j| self.reduced_stiffness[(i, j)]),
+                matrix(m, 1, |i, _| self.reduced_load[i])
+            ),
+        );
+
+        heading(&mut s, "5. Solution");
+        formula(
+            &mut s,
+            &format!(
+                "u_{{red}} = K_{{red}}^{{-1}} P_{{red}} = {}, \\quad u = {}",
+                matrix(m, 1, |i, _| self.reduced_displacements[i]),
+                matrix(n, 1, |i, _| self.displacements[i])
+            ),
+        );
+
+        heading(&mut s, "6. Back substitution");
+        s.push_str(
+            "The displacements u of the points of every beam are transformed into its local \
+             coordinate system, the end forces follow from the local stiffness relation.\n\n",
+        );
+        for b in &self.beams {
+            let v = &b.local_displacements;
+            let f = &b.end_forces;
+            let transformation = if b.offsets.is_some() {
+                format!("T_{{{0}}}^T E_{{{0}}}", b.beam)
+            } else {
+                format!("T_{{{0}}}^T", b.beam)
+            };
+            formula(
+                &mut s,
+                &format!(
+                    "v_{{{0}}} = {1} u_{{{0}}} = {2}, \\quad s_{{{0}}} = k_{{{0}}} v_{{{0}}} + f^0_{{{0}}} = {3}",
+                    b.beam,
+                    transformation,
+                    matrix(6, 1, |i, _| v[i]),
+                    matrix(6, 1, |i, _| f[i])
+                ),
+            );
+        }
+        return s;
+    }
+}
+
+/// Die Matrix als `bmatrix`, Rundungsreste der Kondensation werden zu Null.
+fn matrix(rows: usize, cols: usize, value: impl Fn(usize, usize) -> f64) -> String {
+    let max = (0..rows)
+        .flat_map(|i| (0..cols).map(move |j| (i, j)))
+        .fold(0.0_f64, |m, (i, j)| m.max(value(i, j).abs()));
+    let mut s = String::from("\\begin{bmatrix}");
+    for i in 0..rows {
+        let row: Vec<String> = (0..cols)
+            .map(|j| {
+                let v = value(i, j);
+                number(if v.abs() < 1e-10 * max { 0.0 } else { v })
+            })
+            .collect();
+        s.push_str(&row.join(" & "));
+        if i + 1 < rows {
+            s.push_str(" \\\\ ");
+        }
+    }
+    s.push_str("\\end{bmatrix}");
+    return s;
+}
+
+/// Die Zahl mit vier Nachkommastellen, sehr große und kleine Zahlen mit Zehnerpotenz.
+fn number(v: f64) -> String {
+    if v == 0.0 {
+        return String::from("0");
+    }
+    let e = v.abs().log10().floor();
+    if (-3.0..5.0).contains(&e) {
+        format!("{:.4}", v)
+    } else {
+        format!("{:.3} \\cdot 10^{{{}}}", v / 10f64.powf(e), e)
+    }
+}
+
+impl System {
+    /// The direct stiffness method after first order theory, which records every step for
+    /// teaching: the local stiffness matrices, the transformations, the assembled global matrix,
+    /// the treatment of the boundary conditions, the solution and the back substitution.
+    ///
+    /// The results are the same as from `matrix_stiffness_method_first_order`.
+    pub fn trace_stiffness_method(&self, loading: &SystemLoading) -> StiffnessTrace {
+        let total_dofs = self.get_points().len() * 3;
+        let mut steif = MatrixDxD::zeros(total_dofs, total_dofs);
+        let mut last = VectorD::zeros(total_dofs);
+
+        let mut beams = Vec::new();
+        for i in 0..self.get_beams().len() {
+            let b = &self.get_beams()[i];
+            if !b.is_active() {
+                continue;
+            }
+            let length = self.get_beam_lenght(i);
+            let alpha = self.get_beam_alpha(i);
+            let lineloading = loading.get_total_lineload_for_beam(i);
+            let (k, f) = b.local_stiffness_and_load_first_order(length, Some(lineloading));
+            let (kg, fg) = self.beam_global_stiffness(i, k, f);
+            System::assemble_beam(
+                self.get_beam_from_point(i),
+                self.get_beam_to_point(i),
+                &kg,
+                &fg,
+                &mut steif,
+                &mut last,
+            );
+            let e = self.beam_offset_matrix(i);
+            beams.push(BeamTrace {
+                beam: i,
+                length,
+                alpha,
+                local_stiffness: k,
+                local_load: f,
+                transformation: transmatrix6x6(alpha),
+                offsets: if e == Matrix6x6::identity() {
+                    None
+                } else {
+                    Some(e)
+                },
+                global_stiffness: kg,
+                global_load: fg,
+                local_displacements: Vector6::zeros(),
+                end_forces: Vector6::zeros(),
+            });
+        }
+        System::knotenlasten(loading, &mut last);
+        self.springs(&mut steif, &mut last);
+        self.supports(loading, &mut steif, &mut last);
+
+        // Einarbeiten der Randbedingungen
+        let (k, f, t, u0) = self.reduced_system(loading, &steif, &last);
+        let mut fixed = Vec::new();
+        for i in 0..self.get_supports().len() {
+            let p = self.get_support_points()[i];
+            for j in 0..3 {
+                if self.get_supports()[i].is_fixed(j) {
+                    fixed.push(p * 3 + j);
+                }
+            }
+        }
+        fixed.sort();
+        fixed.dedup();
+        let g = match k.clone().cholesky() {
+            Some(t) => t,
+            None => panic!("Matrix nicht positiv definit."),
+        };
+        let reduced_displacements = g.solve(&f);
+        let result = &t * &reduced_displacements + &u0;
+        let forces = self.constraint_forces(&(&steif * &result - &last));
+
+        // Rückrechnung der Stabendkräfte
+        for bt in &mut beams {
+            bt.local_displacements = self.beam_local_displacements(bt.beam, &result);
+            bt.end_forces = bt.local_stiffness * bt.local_displacements + bt.local_load;
+        }
+        let results = self
+            .beam_results_first_order(loading, &result)
+            .with_constraint_forces(forces);
+
+        StiffnessTrace {
+            beams,
+            stiffness: steif,
+            load: last,
+            fixed,
+            transformation: t,
+            prescribed: u0,
+            reduced_stiffness: k,
+            reduced_load: f,
+            reduced_displacements,
+            displacements: result,
+            results,
+        }
+    }
+}
@@ -0,0 +1,383 @@
+use crate::stiffness::direct_stiffness::{transmatrix3x3, BeamResult, BeamResultSet};
+use crate::stiffness::system::*;
+use nalgebra::DVector;
+use nalgebra::Dynamic;
+use nalgebra::OMatrix;
+
+type MatrixDxD = OMatrix<f64, Dynamic, Dynamic>;
+type VectorD = DVector<f64>;
+
+/// Ein Element der Kette mit der Richtung, in der es durchlaufen wird.
+#[derive(Clone, Copy)]
+enum ChainElement {
+    Beam(usize, bool),
+    Spring(usize, bool),
+}
+
+/// Der Zustand am Knoten als affine Funktion der Unbekannten: die Zeilen sind u_1, u_2, phi_3
+/// und die Kraft F = [F_1, F_2, M_3] des folgenden Teils auf den bisherigen Teil, die letzte
+/// Spalte ist der konstante Anteil.
+struct ChainState {
+    state: MatrixDxD,
+}
+
+impl ChainState {
+    fn displacement(&self) -> MatrixDxD {
+        self.state.rows(0, 3).into_owned()
+    }
+    fn force(&self) -> MatrixDxD {
+        self.state.rows(3, 3).into_owned()
+    }
+    fn new(displacement: MatrixDxD, force: MatrixDxD) -> Self {
+        let mut state = MatrixDxD::zeros(6, displacement.ncols());
+        state.rows_mut(0, 3).copy_from(&displacement);
+        state.rows_mut(3, 3).copy_from(&force);
+        ChainState { state }
+    }
+}
+
+impl System {
+    /// Solves a continuous beam or an unbranched chain of beams and springs with the transfer
+    /// matrix method (Reduktionsverfahren) using first order theory.
+    ///
+    /// The state vector `[u, w, phi, M, Q, N]` is transferred from one end of the chain to the
+    /// other with the field matrices of the beams and the springs. Supports, loads, moment
+    /// releases and springs without stiffness in a direction enter as point matrices with
+    /// intermediate conditions. Rigid offsets, constraints and releases other than moment releases
+    /// are not supported. Like in the stiffness method the fixed dofs of rotated supports act in
+    /// the global directions, springs of rotated supports are not supported.
+    pub fn transfer_matrix_method(&self, loading: &SystemLoading) -> BeamResultSet {
+        if !self.get_constraints().is_empty() {
+            panic!("Zwangsbedingungen werden vom Reduktionsverfahren nicht unterstützt.");
+        }
+        self.check_unrotated_support_springs();
+        self.check_moment_releases();
+        for i in 0..self.get_beams().len() {
+            let o = self.get_beam_global_offsets(i);
+            if o.iter().flatten().any(|&v| v != 0.0) {
+                panic!("Starre Stabenden werden vom Reduktionsverfahren nicht unterstützt.");
+            }
+        }
+        let (start, chain) = self.chain();
+
+        // Unbekannte: Verschiebungen des Anfangspunktes, Lagerkräfte, Knicke der Gelenke und
+        // Klaffungen der Federn ohne Steifigkeit
+        let mut n = 3;
+        for sup in self.get_supports() {
+            n += (0..3).filter(|&d| sup.is_fixed(d)).count();
+        }
+        for &e in &chain {
+            match e {
+                ChainElement::Beam(i, _) => {
+                    n += self.get_beams()[i]
+                        .get_dofs()
+                        .iter()
+                        .filter(|&&d| d)
+                        .count()
+                }
+                ChainElement::Spring(i, _) => {
+                    n += self.get_springs()[i]
+                        .get_stiffness()
+                        .iter()
+                        .filter(|&&k| k == 0.0)
+                        .count()
+                }
+            }
+        }
+        let mut next = 3;
+        let mut conditions: Vec<VectorD> = Vec::new();
+        let unknown = |next: &mut usize| {
+            let mut v = MatrixDxD::zeros(1, n + 1);
+            v[(0, *next)] = 1.0;
+            *next += 1;
+            v
+        };
+
+        let mut u = MatrixDxD::zeros(3, n + 1);
+        for k in 0..3 {
+            u[(k, k)] = 1.0;
+        }
+        let mut node_displacements = vec![None; self.get_points().len()];
+        let mut starts = vec![None; self.get_beams().len()];
+        let mut ends = vec![None; self.get_beams().len()];
+        let mut point = start;
+        let mut force = MatrixDxD::zeros(3, n + 1);
+
+        for step in 0..=chain.len() {
+            // Punktmatrix des Knotens: F_rechts = F_links - P - R
+            for i in 0..loading.get_static_loads().len() {
+                if loading.get_static_load_points()[i] == point {
+                    let p = loading.get_static_loads()[i].get_loading();
+                    for k in 0..3 {
+                        force[(k, n)] -= p[k];
+                    }
+                }
+            }
+            for i in 0..self.get_supports().len() {
+                if self.get_support_points()[i] != point {
+                    continue;
+                }
+                let sup = &self.get_supports()[i];
+                let settlement = loading.get_total_support_displacement(i);
+                for d in 0..3 {
+                    let c = sup.get_contact_displacement(d) + settlement[d];
+                    if sup.is_fixed(d) {
+                        let r = unknown(&mut next);
+                        let mut row = u.row(d).transpose();
+                        row[n] -= c;
+                        conditions.push(row);
+                        let f = force.row(d) - r;
+                        force.set_row(d, &f);
+                    } else if sup.has_spring(d) && sup.get_feder()[d] != 0.0 {
+                        // R = k (c - u)
+                        let k = sup.get_feder()[d];
+                        let mut f = force.row(d) + k * u.row(d);
+                        f[n] -= k * c;
+                        force.set_row(d, &f);
+                    }
+                }
+            }
+            node_displacements[point] = Some(u.clone());
+            if step == chain.len() {
+                break;
+            }
+
+            let state = ChainState::new(u.clone(), force.clone());
+            let (state, end) = match chain[step] {
+                ChainElement::Beam(i, forward) => {
+                    let (z0, zl, s) =
+                        self.transfer_beam(i, forward, &state, loading, &mut next, &mut conditions);
+                    starts[i] = Some(z0);
+                    ends[i] = Some(zl);
+                    let end = if forward {
+                        self.get_beam_to_point(i)
+                    } else {
+                        self.get_beam_from_point(i)
+                    };
+                    (s, end)
+                }
+                ChainElement::Spring(i, forward) => {
+                    let s = self.transfer_spring(i, forward, &state, &mut next, &mut conditions);
+                    let [from, to] = self.get_spring_points()[i];
+                    (s, if forward { to } else { from })
+                }
+            };
+            u = state.displacement();
+            force = state.force();
+            point = end;
+        }
+        // Freies Ende der Kette
+        for k in 0..3 {
+            conditions.push(force.row(k).transpose());
+        }
+
+        let mut a = MatrixDxD::zeros(n, n);
+        let mut b = VectorD::zeros(n);
+        for (r, c) in conditions.iter().enumerate() {
+            for j in 0..n {
+                a[(r, j)] = c[j];
+            }
+            b[r] = -c[n];
+        }
+        let x = match a.lu().solve(&b) {
+            Some(t) => t,
+            None => panic!("Das System ist kinematisch verschieblich."),
+        };
+        let mut x1 = VectorD::zeros(n + 1);
+        x1.rows_mut(0, n).copy_from(&x);
+        x1[n] = 1.0;
+
+        let mut result = VectorD::zeros(self.get_points().len() * 3);
+        for p in 0..self.get_points().len() {
+            if let Some(d) = &node_displacements[p] {
+                result.rows_mut(p * 3, 3).copy_from(&(d * &x1));
+            }
+        }
+        let mut r = Vec::new();
+        for i in 0..self.get_beams().len() {
+            let length = self.get_beam_lenght(i);
+            let b = &self.get_beams()[i];
+            let (z0, zl) = match (&starts[i], &ends[i]) {
+                (Some(z0), Some(zl)) => (z0 * &x1, zl * &x1),
+                _ => {
+                    let v = self.beam_local_displacements(i, &result);
+                    r.push(BeamResult::new_inactive(
+                        &v,
+                        length,
+                        b,
+                        StaticLinearLineload::new_constant_load(0.0),
+                    ));
+                    continue;
+                }
+            };
+            r.push(BeamResult::new(
+                &[z0[5], z0[4], z0[3], zl[5], zl[4], zl[3]],
+                &[z0[0], z0[1], z0[2], zl[0], zl[1], zl[2]],
+                length,
+                b,
+                &loading.get_total_lineload_for_beam(i),
+            ));
+        }
+        return BeamResultSet::new(r).with_displacements(&result);
+    }
+
+    /// Die Reihenfolge der Stäbe und Federn vom Anfangspunkt einer unverzweigten Kette.
+    fn chain(&self) -> (usize, Vec<ChainElement>) {
+        let mut adjacent: Vec<Vec<(ChainElement, usize)>> =
+            vec![Vec::new(); self.get_points().len()];
+        for i in 0..self.get_beams().len() {
+            if !self.get_beams()[i].is_active() {
+                continue;
+            }
+            let from = self.get_beam_from_point(i);
+            let to = self.get_beam_to_point(i);
+            adjacent[from].push((ChainElement::Beam(i, true), to));
+            adjacent[to].push((ChainElement::Beam(i, false), from));
+        }
+        for i in 0..self.get_springs().len() {
+            let [from, to] = self.get_spring_points()[i];
+            adjacent[from].push((ChainElement::Spring(i, true), to));
+            adjacent[to].push((ChainElement::Spring(i, false), from));
+        }
+        let elements = adjacent.iter().map(|a| a.len()).sum::<usize>() / 2;
+        let start = match (0..adjacent.len()).find(|&p| adjacent[p].len() == 1) {
+            Some(t) => t,
+            None => panic!("Das System ist keine unverzweigte Kette."),
+        };
+        if adjacent.iter().any(|a| a.len() > 2) {
+            panic!("Das System ist keine unverzweigte Kette.");
+        }
+
+        let mut chain = Vec::new();
+        let mut visited = vec![false; adjacent.len()];
+        let mut point = start;
+        visited[point] = true;
+        while let Some(&(e, p)) = adjacent[point].iter().find(|(_, p)| !visited[*p]) {
+            chain.push(e);
+            visited[p] = true;
+            point = p;
+        }
+        if chain.len() != elements {
+            panic!("Das System ist keine unverzweigte Kette.");
+        }
+        return (start, chain);
+    }
+
+    /// Feldmatrix eines Stabes mit den Gelenken an seinen Enden. Gibt die lokalen
+    /// Zustandsvektoren [u, w, phi, M, Q, N, 1] am Anfang und am Ende zurück.
+    fn transfer_beam(
+        &self,
+        beamindex: usize,
+        forward: bool,
+        state: &ChainState,
+        loading: &SystemLoading,
+        next: &mut usize,
+        conditions: &mut Vec<VectorD>,
+    ) -> (MatrixDxD, MatrixDxD, ChainState) {
+        let b = &self.get_beams()[beamindex];
+        let length = self.get_beam_lenght(beamindex);
+        let t = System::rotation(self.get_beam_alpha(beamindex));
+        let cols = state.state.ncols();
+        let n = cols - 1;
+
+        let dummy = BeamResult::new(
+            &[0.0; 6],
+            &[0.0; 6],
+            length,
+            b,
+            &loading.get_total_lineload_for_beam(beamindex),
+        );
+        let mut m = dummy.uebertragungsmatrix_first_order(length);
+        let load = dummy.lastvektor_perpendicular_first_order(0.0, length);
+        for i in 0..7 {
+            m[(i, 6)] += load[i];
+        }
+        if !forward {
+            m = match m.try_inverse() {
+                Some(t) => t,
+                None => panic!("Matrix nicht invertierbar."),
+            };
+        }
+
+        // Lokaler Zustand am eintretenden Ende, F = T [N, Q, -M] in Stabrichtung
+        let sign = if forward { 1.0 } else { -1.0 };
+        let d = t.transpose() * state.displacement();
+        let f = sign * t.transpose() * state.force();
+        let mut z = MatrixDxD::zeros(7, cols);
+        z.row_mut(0).copy_from(&d.row(0));
+        z.row_mut(1).copy_from(&d.row(1));
+        z.row_mut(2).copy_from(&d.row(2));
+        z.row_mut(3).copy_from(&(-f.row(2)));
+        z.row_mut(4).copy_from(&f.row(1));
+        z.row_mut(5).copy_from(&f.row(0));
+        z[(6, n)] = 1.0;
+
+        let (entry, exit) = if forward { (2, 5) } else { (5, 2) };
+        if b.get_dofs()[entry] {
+            z[(2, *next)] += 1.0;
+            *next += 1;
+            conditions.push(z.row(3).transpose());
+        }
+        let m = MatrixDxD::from_iterator(7, 7, m.iter().cloned());
+        let mut ze = &m * &z;
+        let mut phi = ze.row(2).into_owned();
+        if b.get_dofs()[exit] {
+            conditions.push(ze.row(3).transpose());
+            phi[(0, *next)] += 1.0;
+            *next += 1;
+        }
+
+        let mut dis = MatrixDxD::zeros(3, cols);
+        dis.row_mut(0).copy_from(&ze.row(0));
+        dis.row_mut(1).copy_from(&ze.row(1));
+        let mut dis = &t * dis;
+        dis.row_mut(2).copy_from(&phi);
+        let mut fl = MatrixDxD::zeros(3, cols);
+        fl.row_mut(0).copy_from(&ze.row(5));
+        fl.row_mut(1).copy_from(&ze.row(4));
+        fl.row_mut(2).copy_from(&(-ze.row(3)));
+        let force = sign * &t * fl;
+        ze[(6, n)] = 1.0;
+
+        let next_state = ChainState::new(dis, force);
+        if forward {
+            (z, ze, next_state)
+        } else {
+            (ze, z, next_state)
+        }
+    }
+
+    /// Die Drehmatrix lokal nach global für die affinen Zustände.
+    fn rotation(alpha: f64) -> MatrixDxD {
+        MatrixDxD::from_iterator(3, 3, transmatrix3x3(alpha).iter().cloned())
+    }
+
+    /// Feldmatrix einer Feder, die Kraft wird unverändert übertragen.
+    fn transfer_spring(
+        &self,
+        springindex: usize,
+        forward: bool,
+        state: &ChainState,
+        next: &mut usize,
+        conditions: &mut Vec<VectorD>,
+    ) -> ChainState {
+        let spring = &self.get_springs()[springindex];
+        let t = System::rotation(spring.get_alpha());
+        let sign = if forward { 1.0 } else { -1.0 };
+        // Federkraft f = k (v_to - v_from) im gedrehten KOS
+        let f = sign * t.transpose() * state.force();
+        let mut jump = MatrixDxD::zeros(3, f.ncols());
+        for d in 0..3 {
+            let k = spring.get_stiffness()[d];
+            if k == 0.0 {
+                conditions.push(f.row(d).transpose());
+                jump[(d, *next)] = 1.0;
+                *next += 1;
+            } else {
+                jump.row_mut(d).copy_from(&(f.row(d) / k));
+            }
+        }
+        let jump = t * jump;
+        ChainState::new(state.displacement() + sign * jump, state.force())
+    }
+}
@@ -0,0 +1,118 @@
+use crate::stiffness::direct_stiffness::BeamResultSet;
+use crate::stiffness::system::*;
+
+const MAX_ITERATIONS: usize = 100;
+
+pub struct UnilateralResultSet {
+    results: BeamResultSet,
+    active_beams: Vec<bool>,
+    separated_supports: Vec<bool>,
+    iterations: usize,
+    converged: bool,
+}
+
+impl UnilateralResultSet {
+    pub fn get_results(&self) -> &BeamResultSet {
+        &self.results
+    }
+    /// The final active set, inactive beams have dropped out of the load transfer.
+    pub fn get_active_beams(&self) -> &[bool] {
+        &self.active_beams
+    }
+    /// Which supports have separated from their points, e.g. by lift-off.
+    pub fn get_separated_supports(&self) -> &[bool] {
+        &self.separated_supports
+    }
+    pub fn get_iterations(&self) -> usize {
+        self.iterations
+    }
+    pub fn is_converged(&self) -> bool {
+        self.converged
+    }
+}
+
+impl System {
+    /// Solves the system with tension-only and compression-only members and unilateral supports
+    /// using first order theory.
+    ///
+    /// Members with the wrong sign of the normal force are deactivated, inactive members are
+    /// reactivated, if the elongation between their nodes has the right sign again. Supports
+    /// separate, if their reaction pulls (or the spring is stretched), and close again, if the
+    /// point moves through the gap. This is repeated until the state is stable. The system has to
+    /// stay stable without the deactivated members and separated supports.
+    pub fn matrix_stiffness_method_unilateral(
+        &self,
+        loading: &SystemLoading,
+    ) -> UnilateralResultSet {
+        let mut sys = self.clone();
+        let mut iterations = 0;
+        let mut converged = false;
+        let mut res = sys.matrix_stiffness_method_first_order(loading);
+
+        while iterations < MAX_ITERATIONS {
+            iterations += 1;
+            let mut changed = false;
+            for i in 0..sys.get_beams().len() {
+                let b = sys.get_beams()[i];
+                let r = &res.get_results()[i];
+                // Normalkraft bei aktiven, Längenänderung bei ausgefallenen Stäben
+                let value = if b.is_active() {
+                    r.get_rsks()[0]
+                } else {
+                    r.get_rvs()[3] - r.get_rvs()[0]
+                };
+                let active = match b.get_axial_behaviour() {
+                    AxialBehaviour::TensionAndCompression => true,
+                    AxialBehaviour::TensionOnly => value > 0.0,
+                    AxialBehaviour::CompressionOnly => value < 0.0,
+                };
+                if active != b.is_active() {
+                    sys.get_beams_mut()[i].set_active(active);
+                    changed = true;
+                }
+            }
+            let reactions = sys.support_reactions(loading, &res);
+            for i in 0..sys.get_supports().len() {
+                let sup = &sys.get_supports()[i];
+                let dof = match sup.get_unilateral_dof() {
+                    Some(t) => t,
+                    None => continue,
+                };
+                let dir = sup.get_unilateral_direction();
+                let p = sys.get_support_points()[i];
+                let u = res.get_node_displacements()[p][dof] * dir;
+                let separated = if sup.is_separated() {
+                    // Die Klaffung ist noch offen
+                    u < sup.get_gap()
+                } else if sup.get_free_dofs()[dof] {
+                    // Feder wird gezogen
+                    u < sup.get_gap()
+                } else {
+                    // Das Lager zieht am Punkt
+                    reactions[i][dof] * dir > 0.0
+                };
+                if separated != sup.is_separated() {
+                    sys.get_supports_mut()[i].set_separated(separated);
+                    changed = true;
+                }
+            }
+            if !changed {
+                converged = true;
+                break;
+            }
+            res = sys.matrix_stiffness_method_first_order(loading);
+        }
+
+        UnilateralResultSet {
+            results: res,
+            active_beams: sys.get_beams().iter().map(|b| b.is_active()).collect(),
+            separated_supports: sys
+                .get_supports()
+                .iter()
+                .map(|s| s.is_separated())
+                .collect(),
+            iterations,
+            converged,
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::stiffness::direct_stiffness::BeamResultSet;
+use crate::stiffness::system::*;
+
+/// Die Stützstellen und Gewichte der Gauß-Legendre-Quadratur mit drei Punkten auf [0, 1], exakt
+/// für Polynome bis zum fünften Grad.
+const GAUSS: [(f64, f64); 3] = [
+    (0.1127016653792583, 0.2777777777777778),
+    (0.5, 0.4444444444444444),
+    (0.8872983346207417, 0.2777777777777778),
+];
+
+impl System {
+    /// The displacement `dof` (x_1, x_2, phi_3 in the global coordinate system) at the position
+    /// `x` of the beam after the principle of virtual work (Arbeitssatz), with the `results` of a
+    /// loading after first order theory.
+    ///
+    /// The virtual state is the solution for a unit load at the position, which is added as
+    /// point of a copy of the system. The products of the real and the virtual moments and normal
+    /// forces are integrated numerically along the beams, the springs, the spring releases of the
+    /// beams and the displacements of the supports are included. At the ends of a beam the
+    /// displacement of the face is returned, the rotation at a released end is the one of the
+    /// point.
+    pub fn virtual_work_displacement(
+        &self,
+        results: &BeamResultSet,
+        beamindex: usize,
+        x: f64,
+        dof: usize,
+    ) -> f64 {
+        let l = self.get_beam_lenght(beamindex);
+        let mut positions = vec![Vec::new(); self.get_beams().len()];
+        positions[beamindex].push(x);
+        let (sys, parts) = self.subdivide(&positions);
+
+        // Virtueller Zustand: die Einheitslast an der Stelle
+        let mut unit = [0.0; 3];
+        unit[dof] = 1.0;
+        let point = sys.point_at(&parts[beamindex], x, l);
+        let load = self.face_load(beamindex, x, &StaticLoad::new(unit[0], unit[1], unit[2]));
+        let virtual_loading = SystemLoading::new(vec![point], vec![load], Vec::new(), Vec::new());
+        let virtual_results = sys.matrix_stiffness_method_first_order(&virtual_loading);
+
+        let mut work = 0.0;
+        for b in 0..self.get_beams().len() {
+            let beam = &self.get_beams()[b];
+            if !beam.is_active() {
+                continue;
+            }
+            let real = &results.get_results()[b];
+            let ei = beam.get_emodul() * beam.get_ftm();
+            let ea = beam.get_emodul() * beam.get_area();
+            let length = self.get_beam_lenght(b);
+            for k in 0..parts[b].len() {
+                let (part, x0) = parts[b][k];
+                let x1 = if k + 1 < parts[b].len() {
+                    parts[b][k + 1].1
+                } else {
+                    length
+                };
+                let virt = &virtual_results.get_results()[part];
+                for (xi, w) in GAUSS {
+                    let s = (x1 - x0) * xi;
+                    let r = real.get_internals_at(x0 + s);
+                    let v = virt.get_internals_at(s);
+                    work += w * (x1 - x0) * (r[2] * v[2] / ei + r[0] * v[0] / ea);
+                }
+            }
+            // Federn der Gelenke an den Stabenden
+            for d in 0..6 {
+                let k = beam.get_dofstiffness()[d];
+                if !beam.get_dofs()[d] || k == 0.0 {
+                    continue;
+                }
+                let (part, x0, x) = if d < 3 {
+                    (parts[b][0].0, 0.0, 0.0)
+                } else {
+                    let (part, x0) = parts[b][parts[b].len() - 1];
+                    (part, x0, length)
+                };
+                let c = d % 3;
+                let r = real.get_internals_at(x)[c];
+                let v = virtual_results.get_results()[part].get_internals_at(x - x0)[c];
+                work += r * v / k;
+            }
+        }
+
+        // Federn zwischen Punkten
+        let real_springs = self.spring_forces(results);
+        let virtual_springs = sys.spring_forces(&virtual_results);
+        for i in 0..self.get_springs().len() {
+            for d in 0..3 {
+                let k = self.get_springs()[i].get_stiffness()[d];
+                if k != 0.0 {
+                    work += real_springs[i][d] * virtual_springs[i][d] / k;
+                }
+            }
+        }
+
+        // Arbeit der virtuellen Lagerkräfte an den Lagerverschiebungen
+        let reactions = sys.support_reactions(&virtual_loading, &virtual_results);
+        for i in 0..self.get_supports().len() {
+            let p = self.get_support_points()[i];
+            for d in 0..3 {
+                work -= reactions[i][d] * results.get_node_displacements()[p][d];
+            }
+        }
+        return work;
+    }
+}